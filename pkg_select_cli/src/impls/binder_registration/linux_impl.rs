@@ -3,11 +3,12 @@
 use std::fs::{create_dir_all, remove_file};
 use std::os::unix::fs::symlink;
 use std::path::PathBuf;
+use crate::impls::registration_execution_impl::backup_path;
 use crate::models::binder_registration_resolver::BinderRegistrationResolver;
 use crate::models::errors::binder_registration::BinderRegistrationError;
 use crate::models::errors::binder_registration::Type::RunnerNotFound;
 use crate::models::errors::binder_registration_combo::IOBinderRegistrationError;
-use crate::traits::binder_registration::BinderRegistration;
+use crate::traits::binder_registration::{BinderRegistration, BinderRegistrationDescription};
 
 impl BinderRegistration for BinderRegistrationResolver {
     fn registered(&self, exec_path: &PathBuf, process_path_without_filename: &PathBuf, source_name: &String) -> Result<bool, IOBinderRegistrationError> {
@@ -82,7 +83,7 @@ impl BinderRegistration for BinderRegistrationResolver {
     }
 
     fn unregister(&self, exec_path: &PathBuf) -> Result<(), IOBinderRegistrationError> {
-        if (&exec_path).exists() {
+        if (&exec_path).exists() || (&exec_path).is_symlink() {
             match remove_file(&exec_path) {
                 Ok(_) => {}
                 Err(error) => {
@@ -98,3 +99,24 @@ impl BinderRegistration for BinderRegistrationResolver {
         Ok(())
     }
 }
+
+impl BinderRegistrationDescription for BinderRegistrationResolver {
+    fn describe_register(&self, exec_path: &PathBuf, process_path_without_filename: &PathBuf, source_name: &String) -> Vec<String> {
+        let runner_path = process_path_without_filename.join(source_name);
+        let mut changes: Vec<String> = vec![];
+
+        if (&exec_path).exists() || (&exec_path).is_symlink() {
+            changes.push(format!("move file {} to {}", exec_path.to_string_lossy(), backup_path(exec_path).to_string_lossy()));
+        }
+
+        changes.push(
+            format!("create symlink {} -> {}", exec_path.to_string_lossy(), runner_path.to_string_lossy())
+        );
+
+        changes
+    }
+
+    fn describe_unregister(&self, exec_path: &PathBuf) -> Vec<String> {
+        vec![format!("remove symlink {}", exec_path.to_string_lossy())]
+    }
+}
@@ -5,11 +5,12 @@ use std::cmp::Ordering;
 use std::fs::{create_dir_all, File, remove_file};
 use std::io::{BufReader, ErrorKind, Read};
 use std::path::PathBuf;
+use crate::impls::registration_execution_impl::backup_path;
 use crate::models::binder_registration_resolver::BinderRegistrationResolver;
 use crate::models::errors::binder_registration::BinderRegistrationError;
 use crate::models::errors::binder_registration::Type::RunnerNotFound;
 use crate::models::errors::binder_registration_combo::IOBinderRegistrationError;
-use crate::traits::binder_registration::BinderRegistration;
+use crate::traits::binder_registration::{BinderRegistration, BinderRegistrationDescription};
 
 impl BinderRegistration for BinderRegistrationResolver {
     fn registered(&self, exec_path: &PathBuf, process_path_without_filename: &PathBuf, source_name: &String) -> Result<bool, IOBinderRegistrationError> {
@@ -188,3 +189,24 @@ fn match_files(target1: &PathBuf, target2: &PathBuf) -> Result<bool, io::Error>
         }
     }
 }
+
+impl BinderRegistrationDescription for BinderRegistrationResolver {
+    fn describe_register(&self, exec_path: &PathBuf, process_path_without_filename: &PathBuf, source_name: &String) -> Vec<String> {
+        let runner_path = process_path_without_filename.join(source_name);
+        let mut changes: Vec<String> = vec![];
+
+        if (&exec_path).exists() {
+            changes.push(format!("move file {} to {}", exec_path.to_string_lossy(), backup_path(exec_path).to_string_lossy()));
+        }
+
+        changes.push(
+            format!("copy runner {} to {}", runner_path.to_string_lossy(), exec_path.to_string_lossy())
+        );
+
+        changes
+    }
+
+    fn describe_unregister(&self, exec_path: &PathBuf) -> Vec<String> {
+        vec![format!("remove runner copy {}", exec_path.to_string_lossy())]
+    }
+}
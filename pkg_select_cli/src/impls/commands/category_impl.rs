@@ -0,0 +1,21 @@
+use std::rc::Rc;
+use crate::models::commands::category::Category;
+use crate::models::commands::update_subcommands::package_category::PackageCategorySetDefault;
+use crate::models::trait_combos::command_combo::CommandOrCollection;
+use crate::traits::command_collection::CommandCollection;
+
+impl CommandCollection for Category {
+    fn collection_names(&self) -> Vec<&str> {
+        vec!["category"]
+    }
+
+    fn description(&self) -> &str {
+        "Change package categories and save them right away, without update."
+    }
+
+    fn commands(&self) -> Vec<CommandOrCollection> {
+        vec![
+            CommandOrCollection::Command(Rc::new(PackageCategorySetDefault {})),
+        ]
+    }
+}
@@ -0,0 +1,502 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use colored::Colorize;
+use pkg_select_shared::common_models::models::configurations::env_var::EnvValue;
+use pkg_select_shared::common_models::models::configurations::package_category::PackageCategory;
+use pkg_select_shared::common_models::models::configurations::runnable_package::RunnablePackage;
+use pkg_select_shared::common_models::models::configurations::target_binder::TargetBinder;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
+use pkg_select_shared::common_models::traits::validator::Validator;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::{is_executable_file, log_info, log_warn, MutationLocker, safe_canonicalize, user_home_dir};
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use crate::impls::commands::update_subcommands::commit_changes_impl::collect_exec_paths;
+use crate::impls::registration_execution_impl::registration_executor;
+use crate::models::commands::discover::{Discover, DiscoverJdk};
+use crate::models::command_option::CommandOption;
+use crate::models::errors::command::CommandError;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
+use crate::models::trait_combos::command_combo::CommandOrCollection;
+use crate::traits::cli_command::CLICommand;
+use crate::traits::command_collection::CommandCollection;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::plan::{plan_options, print_plan};
+use crate::utils::validation::collect_report;
+
+const JDK_CATEGORY_NAME: &str = "jdk";
+
+impl CommandCollection for Discover {
+    fn collection_names(&self) -> Vec<&str> {
+        vec!["discover"]
+    }
+
+    fn description(&self) -> &str {
+        "Find installed toolchains and add them as packages."
+    }
+
+    fn commands(&self) -> Vec<CommandOrCollection> {
+        vec![
+            CommandOrCollection::Command(Rc::new(DiscoverJdk {})),
+        ]
+    }
+}
+
+impl CLICommand for DiscoverJdk {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["jdk"]
+    }
+
+    fn description(&self) -> &str {
+        "Find JDKs by their release files and add a package for each of them into category jdk. Outside of update, it is committed right away."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![
+            CommandOption {
+                names: vec!["--exec-path"],
+                value: Some("path"),
+                description: "Execution path of the binders. Defaults to the one category jdk already uses.",
+            },
+            CommandOption {
+                names: vec!["--root"],
+                value: Some("paths"),
+                description: "Extra directories to search, separated like PATH. Each may be a JDK or contain JDKs.",
+            },
+        ];
+        options.append(&mut plan_options());
+
+        options
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let json = args.optional_flag(format!("--json"));
+        let dry_run = json || args.optional_flag(format!("--dry-run"));
+        let exec = args.optional_argument(format!("--exec-path"));
+        let jdks = discover_jdks(&jdk_roots(&args.optional_argument(format!("--root"))));
+
+        // Changes in update are saved by commit, otherwise they are committed here.
+        match config.value_mut() {
+            Some(value) => {
+                if dry_run {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("--dry-run and --json can't be used inside update. Run commit --dry-run to see planned changes.")
+                        )
+                    ]);
+                }
+
+                return add_jdks(&jdks, &exec, value).map(| _ | ());
+            }
+            None => {}
+        }
+
+        let existed = config.value().clone();
+        let mut updated = existed.clone();
+
+        if add_jdks(&jdks, &exec, &mut updated)? == 0 {
+            return match dry_run {
+                true => print_plan("discover jdk", &vec![], json),
+                false => Ok(()),
+            };
+        }
+
+        let mut errors: Vec<CommandError> = vec![];
+        collect_report(updated.validate(), false, &mut errors)?;
+
+        if !errors.is_empty() {
+            errors.insert(
+                0,
+                CommandError::String(
+                    format!("failed validation before saving...")
+                )
+            );
+
+            return Err(errors);
+        }
+
+        updated.package_category_hash = match updated.hash() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't get hash for changed config.")
+                    ),
+                    CommandError::Others(Box::new(error))
+                ]);
+            }
+        };
+
+        let mut executor = registration_executor(resolver)?;
+
+        let mut plan = RegistrationPlan::default();
+        plan.steps.append(&mut executor.plan_register(&collect_exec_paths(&updated))?);
+        plan.steps.push(RegistrationStep::SaveConfig {
+            config: Box::new(updated),
+            previous: Box::new(existed),
+        });
+
+        if dry_run {
+            return print_plan("discover jdk", &executor.describe(&plan), json);
+        }
+
+        executor.execute(&plan)?;
+
+        println!("Saved changes successfully! ({} steps applied)", plan.steps.len());
+
+        Ok(())
+    }
+}
+
+/**
+   Usual locations of JDKs on Linux, SDKMAN! and IntelliJ IDEA, followed by the extra ones.
+**/
+fn jdk_roots(extra_roots: &Option<String>) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = vec![
+        PathBuf::from("/usr/lib/jvm"),
+        PathBuf::from("/opt"),
+    ];
+
+    // JDKs of the user who ran sudo are discovered rather than those of root, like config is.
+    match user_home_dir() {
+        Ok(value) => {
+            roots.push(value.join(".sdkman").join("candidates").join("java"));
+            roots.push(value.join(".jdks"));
+        }
+        Err(_) => {}
+    }
+
+    match extra_roots {
+        None => {}
+        Some(value) => roots.extend(std::env::split_paths(value)),
+    }
+
+    roots
+}
+
+/**
+   Returns canonical home and fields of release file of every JDK found in roots or as roots themselves.
+   Homes are canonicalized, so that links like current of SDKMAN! aren't found twice.
+**/
+fn discover_jdks(roots: &Vec<PathBuf>) -> Vec<(PathBuf, HashMap<String, String>)> {
+    let mut jdks: Vec<(PathBuf, HashMap<String, String>)> = vec![];
+
+    for value in roots.iter() {
+        let mut candidates: Vec<PathBuf> = vec![value.clone()];
+
+        match fs::read_dir(value) {
+            Ok(value2) => {
+                let mut children: Vec<PathBuf> = value2
+                    .filter_map(| value3 | value3.ok())
+                    .map(| value3 | value3.path())
+                    .collect();
+                children.sort();
+
+                candidates.append(&mut children);
+            }
+            Err(_) => continue,
+        }
+
+        for value2 in candidates.iter() {
+            // JDKs on macOS keep their home under Contents/Home.
+            let home = match jdk_home(value2).or(jdk_home(&value2.join("Contents").join("Home"))) {
+                None => continue,
+                Some(value3) => value3,
+            };
+
+            if jdks.iter().any(| (value3, _) | value3.eq(&home)) {
+                continue;
+            }
+
+            match fs::read_to_string(home.join("release")) {
+                Ok(value3) => jdks.push((home, parse_release(&value3))),
+                Err(_) => {}
+            }
+        }
+    }
+
+    jdks
+}
+
+fn jdk_home(path: &Path) -> Option<PathBuf> {
+    let has_java = is_executable_file(&path.join("bin").join("java"))
+        || is_executable_file(&path.join("bin").join("java.exe"));
+
+    match path.join("release").is_file() && has_java {
+        true => safe_canonicalize(&path.to_path_buf()).ok(),
+        false => None,
+    }
+}
+
+/**
+   Release file consists of lines like JAVA_VERSION="17.0.8".
+**/
+fn parse_release(raw_data: &str) -> HashMap<String, String> {
+    raw_data.lines()
+        .filter_map(| value | value.split_once("="))
+        .map(| (key, value) | (key.trim().to_string(), value.trim().trim_matches('"').to_string()))
+        .collect()
+}
+
+/**
+   Name like eclipse-adoptium-17.0.8 from IMPLEMENTOR and JAVA_VERSION, or the directory name if they are missing.
+**/
+fn jdk_package_name(home: &Path, release: &HashMap<String, String>) -> String {
+    let raw_name = match (release.get("IMPLEMENTOR"), release.get("JAVA_VERSION")) {
+        (Some(value), Some(value2)) => format!("{}-{}", value, value2),
+        (None, Some(value)) => format!("jdk-{}", value),
+        _ => home.file_name()
+            .map(| value | value.to_string_lossy().to_string())
+            .unwrap_or(format!("jdk")),
+    };
+
+    let mut name = String::new();
+
+    for value in raw_name.to_lowercase().chars() {
+        match value.is_ascii_alphanumeric() || value == '.' {
+            true => name.push(value),
+            false => {
+                if !name.is_empty() && !name.ends_with('-') {
+                    name.push('-');
+                }
+            }
+        }
+    }
+
+    name.trim_end_matches('-').to_string()
+}
+
+/**
+   Add a package for every JDK which is not added yet, and returns how many of them are added.
+**/
+fn add_jdks(
+    jdks: &Vec<(PathBuf, HashMap<String, String>)>,
+    exec: &Option<String>,
+    config: &mut RuntimeConfig,
+) -> Result<usize, Vec<CommandError>> {
+    if jdks.is_empty() {
+        log_warn!("Couldn't find any JDK.");
+        log_info!("Hint: add --root=<path> to search other directories.");
+
+        return Ok(0);
+    }
+
+    let exec_path = match jdk_exec_path(config.package_categories.iter().find(| value | value.name.eq(JDK_CATEGORY_NAME)), exec) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(error);
+        }
+    };
+    let execution_path = exec_path.to_string_lossy().to_string();
+
+    if !config.package_categories.iter().any(| value | value.name.eq(JDK_CATEGORY_NAME)) {
+        config.package_categories.push(PackageCategory {
+            name: JDK_CATEGORY_NAME.to_string(),
+            ..Default::default()
+        });
+
+        log_info!("Added package category {}.", JDK_CATEGORY_NAME);
+    }
+
+    let mut names: Vec<String> = vec![];
+    for value in config.package_categories.iter() {
+        names.push(value.name.to_string());
+
+        for value2 in value.packages.iter() {
+            names.push(value2.name.to_string());
+        }
+    }
+
+    let category = config.package_categories.iter_mut()
+        .find(| value | value.name.eq(JDK_CATEGORY_NAME))
+        .unwrap();
+
+    log_info!("Found {} JDKs:", jdks.len());
+
+    let mut added_count = 0;
+
+    for (home, release) in jdks.iter() {
+        let existing = category.packages.iter().find(| value | {
+            value.interpolated().0.home
+                .and_then(| value2 | safe_canonicalize(&PathBuf::from(value2)).ok())
+                .map_or(false, | value2 | value2.eq(home))
+        });
+
+        match existing {
+            Some(value) => {
+                log_info!("  {} {} (already added as {})", "=".dimmed(), home.to_string_lossy(), value.name);
+                continue;
+            }
+            None => {}
+        }
+
+        let base_name = jdk_package_name(home, release);
+        let mut name = base_name.to_string();
+        let mut suffix = 2;
+
+        while names.contains(&name) {
+            name = format!("{}-{}", base_name, suffix);
+            suffix += 1;
+        }
+
+        let mut target_names: Vec<String> = match fs::read_dir(home.join("bin")) {
+            Ok(value) => value
+                .filter_map(| value2 | value2.ok())
+                .filter(| value2 | is_executable_file(&value2.path()))
+                .map(| value2 | value2.file_name().to_string_lossy().to_string())
+                .collect(),
+            Err(_) => vec![],
+        };
+        target_names.sort();
+
+        let package = RunnablePackage {
+            name: name.to_string(),
+            home: Some(home.to_string_lossy().to_string()),
+            envs: HashMap::from([(format!("JAVA_HOME"), EnvValue::set("${PACKAGE_HOME}"))]),
+            binders: target_names.iter()
+                .map(| value | TargetBinder {
+                    target_name: value.to_string(),
+                    target_path: format!("bin"),
+                    execution_path: execution_path.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        log_info!(
+            "  {} {} ({}, {} binders)",
+            "+".green(),
+            name,
+            home.to_string_lossy(),
+            package.binders.len()
+        );
+
+        if category.default_package.is_none() {
+            category.default_package = Some(name.to_string());
+        }
+
+        category.packages.push(package);
+        names.push(name);
+        added_count += 1;
+    }
+
+    match added_count {
+        0 => log_info!("Every JDK is already added into category {}.", JDK_CATEGORY_NAME),
+        _ => log_info!("Added {} JDKs into category {}.", added_count, JDK_CATEGORY_NAME),
+    }
+
+    Ok(added_count)
+}
+
+/**
+   Execution path given, or the one every binder of the category already uses so that discovery can be run again.
+**/
+fn jdk_exec_path(category: Option<&PackageCategory>, exec: &Option<String>) -> Result<PathBuf, Vec<CommandError>> {
+    let raw_exec = match exec {
+        Some(value) => value.to_string(),
+        None => {
+            let mut exec_paths: Vec<&String> = category.iter()
+                .flat_map(| value | value.packages.iter())
+                .flat_map(| value | value.binders.iter())
+                .map(| value | &value.execution_path)
+                .collect();
+            exec_paths.sort();
+            exec_paths.dedup();
+
+            match exec_paths.len() {
+                1 => exec_paths[0].to_string(),
+                _ => {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("execution path is undefined.")
+                        ),
+                        CommandError::String(
+                            format!("Hint: add --exec-path=<path>")
+                        )
+                    ]);
+                }
+            }
+        }
+    };
+
+    match safe_canonicalize(&PathBuf::from(raw_exec)) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(vec![
+            CommandError::Others(Box::new(error))
+        ]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+    use super::*;
+
+    fn release(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields.iter()
+            .map(| (key, value) | (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn release_fields_are_parsed_without_quotes() {
+        let fields = parse_release("IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"17.0.8\"\n\nOS_ARCH = x86_64\nbroken line\n");
+
+        assert_eq!(fields, release(&[
+            ("IMPLEMENTOR", "Eclipse Adoptium"),
+            ("JAVA_VERSION", "17.0.8"),
+            ("OS_ARCH", "x86_64"),
+        ]));
+    }
+
+    #[test]
+    fn package_name_is_made_from_implementor_and_version() {
+        let home = Path::new("/opt/jdk-17");
+
+        assert_eq!(
+            jdk_package_name(home, &release(&[("IMPLEMENTOR", "Eclipse Adoptium"), ("JAVA_VERSION", "17.0.8")])),
+            "eclipse-adoptium-17.0.8"
+        );
+        assert_eq!(
+            jdk_package_name(home, &release(&[("IMPLEMENTOR", "Azul Systems"), ("JAVA_VERSION", "11.0.20+8")])),
+            "azul-systems-11.0.20-8"
+        );
+        assert_eq!(jdk_package_name(home, &release(&[("JAVA_VERSION", "21")])), "jdk-21");
+    }
+
+    #[test]
+    fn package_name_falls_back_to_directory_name() {
+        let fields = release(&[("IMPLEMENTOR", "Oracle Corporation")]);
+
+        assert_eq!(jdk_package_name(Path::new("/opt/Temurin_21"), &fields), "temurin-21");
+        assert_eq!(jdk_package_name(Path::new("/"), &fields), "jdk");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn jdks_are_found_in_roots_once() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let root = std::env::temp_dir().join(format!("pkg-select-discover-{}", process::id()));
+        let home = root.join("temurin-17");
+        fs::create_dir_all(home.join("bin")).unwrap();
+        fs::create_dir_all(root.join("not-a-jdk")).unwrap();
+        fs::write(home.join("release"), "JAVA_VERSION=\"17.0.8\"\n").unwrap();
+        fs::write(home.join("bin").join("java"), "").unwrap();
+        fs::set_permissions(home.join("bin").join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+        symlink(&home, root.join("current")).unwrap();
+
+        let jdks = discover_jdks(&vec![root.clone()]);
+
+        assert_eq!(jdks.len(), 1);
+        assert_eq!(jdks[0].0, fs::canonicalize(&home).unwrap());
+        assert_eq!(jdks[0].1, release(&[("JAVA_VERSION", "17.0.8")]));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,495 @@
+use std::fs::read_dir;
+use std::path::PathBuf;
+use colored::Colorize;
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::impls::interpolation_impl::refers_to_env;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
+use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::MutationLocker;
+use crate::impls::commands::update_subcommands::commit_changes_impl::collect_exec_paths;
+use crate::impls::registration_execution_impl::{binder_registered, path_registered, registration_executor};
+use crate::models::commands::doctor::Doctor;
+use crate::models::command_option::CommandOption;
+use crate::models::discrepancy::Discrepancy;
+use crate::models::errors::command::CommandError;
+use crate::models::registration_executor::RegistrationExecutor;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
+use crate::traits::cli_command::CLICommand;
+use crate::traits::path_registration_description::PathRegistrationInventory;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::plan::{plan_options, print_plan};
+
+impl CLICommand for Doctor {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["doctor"]
+    }
+
+    fn description(&self) -> &str {
+        "Find differences between config and registrations of runners and paths, and repair them with --fix."
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![
+            CommandOption {
+                names: vec!["--fix"],
+                value: None,
+                description: "Repair every discrepancy that can be repaired.",
+            },
+            CommandOption {
+                names: vec!["--prune"],
+                value: None,
+                description: "With --fix, remove binders whose target doesn't exist from config.",
+            },
+        ];
+        options.append(&mut plan_options());
+
+        options
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let fix = args.optional_flag(format!("--fix"));
+        let prune = args.optional_flag(format!("--prune"));
+        let json = args.optional_flag(format!("--json"));
+        let dry_run = json || args.optional_flag(format!("--dry-run"));
+
+        let mut executor = registration_executor(resolver)?;
+        let discrepancies = inspect(&executor, config.value())?;
+
+        if fix {
+            let plan = fix_plan(&executor, config.value(), &discrepancies, prune)?;
+
+            if dry_run {
+                return print_plan("doctor", &executor.describe(&plan), json);
+            }
+
+            print_discrepancies(&discrepancies, prune);
+            executor.execute(&plan)?;
+
+            let unfixed = discrepancies.iter()
+                .filter(| value | !fixable(value, prune))
+                .count();
+
+            if discrepancies.len() > unfixed {
+                println!("Fixed {} discrepancies.", discrepancies.len() - unfixed);
+            }
+
+            if unfixed > 0 {
+                let mut errors = vec![
+                    CommandError::String(
+                        format!("{} discrepancies must be fixed manually.", unfixed)
+                    )
+                ];
+
+                if !prune && discrepancies.iter().any(| value | matches!(value, Discrepancy::MissingTarget { .. })) {
+                    errors.push(
+                        CommandError::String(
+                            format!("run doctor --fix --prune to remove binders whose target doesn't exist from config.")
+                        )
+                    );
+                }
+
+                return Err(errors);
+            }
+
+            return Ok(());
+        }
+
+        print_discrepancies(&discrepancies, prune);
+
+        if !discrepancies.is_empty() {
+            return Err(vec![
+                CommandError::String(
+                    format!("found {} discrepancies. Run doctor --fix to repair them.", discrepancies.len())
+                )
+            ]);
+        }
+
+        Ok(())
+    }
+}
+
+fn print_discrepancies(discrepancies: &Vec<Discrepancy>, prune: bool) {
+    if discrepancies.is_empty() {
+        println!("No discrepancy found.");
+        return;
+    }
+
+    println!("Found {} discrepancies:", discrepancies.len());
+
+    for value in discrepancies.iter() {
+        match (fixable(value, prune), value) {
+            (true, _) => println!("  - {}", value),
+            (false, Discrepancy::MissingTarget { .. }) => println!("  - {} {}", value, "(fix manually or with --prune)".yellow()),
+            (false, _) => println!("  - {} {}", value, "(fix manually)".yellow()),
+        }
+    }
+}
+
+/**
+   Removing binders from config loses them, so binders whose target is missing are fixed only when pruning is asked.
+**/
+fn fixable(discrepancy: &Discrepancy, prune: bool) -> bool {
+    match discrepancy {
+        Discrepancy::ForeignFile(_) => false,
+        Discrepancy::MissingTarget { .. } => prune,
+        _ => true,
+    }
+}
+
+fn inspect(executor: &RegistrationExecutor, config: &RuntimeConfig) -> Result<Vec<Discrepancy>, Vec<CommandError>> {
+    let (mut discrepancies, exec_paths, inspected_paths) = inspect_binders(config);
+
+    for value in inspected_paths.iter() {
+        match inspect_runner(executor, value)? {
+            None => {}
+            Some(value2) => discrepancies.push(value2),
+        }
+
+        if !path_registered(executor, value)? {
+            discrepancies.push(Discrepancy::MissingPath(value.clone()));
+        }
+    }
+
+    let registered_paths = match executor.path_registration_resolver.registered_paths() {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![CommandError::Others(Box::new(error))]);
+        }
+    };
+
+    for value in registered_paths.iter() {
+        if !covered_path(value, &exec_paths) && owned_path(executor, value) {
+            discrepancies.push(Discrepancy::OrphanPath(value.clone()));
+        }
+    }
+
+    let mut dirs: Vec<PathBuf> = vec![];
+
+    for value in exec_paths.iter().chain(registered_paths.iter()) {
+        let dir = match registered_dir(value, &exec_paths) {
+            None => continue,
+            Some(value2) => value2,
+        };
+
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    for value in dirs.iter() {
+        let entries = match read_dir(value) {
+            Ok(value2) => value2,
+            Err(_) => continue,
+        };
+
+        for value2 in entries {
+            let path = match value2 {
+                Ok(value3) => value3.path(),
+                Err(_) => continue,
+            };
+
+            if exec_paths.contains(&path) {
+                continue;
+            }
+
+            if is_runner_file(executor, &path) {
+                discrepancies.push(Discrepancy::OrphanRunner(path));
+            }
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+/**
+   Returns binders whose target is missing, every exec path, and exec paths whose runner and path must be inspected.
+   Packages usually share exec paths, so each of them is inspected only once, or it would be repaired more than once.
+**/
+fn inspect_binders(config: &RuntimeConfig) -> (Vec<Discrepancy>, Vec<PathBuf>, Vec<PathBuf>) {
+    let mut discrepancies: Vec<Discrepancy> = vec![];
+    let mut exec_paths: Vec<PathBuf> = vec![];
+    let mut inspected_paths: Vec<PathBuf> = vec![];
+
+    for value in config.package_categories.iter() {
+        for value2 in value.packages.iter() {
+            let (package, _) = value2.interpolated();
+
+            for (index, value3) in package.binders.iter().enumerate() {
+                let exec_path = value3.convert_exec_to_pathbuf();
+                let target_path = value3.convert_target_to_pathbuf();
+
+                if !exec_paths.contains(&exec_path) {
+                    exec_paths.push(exec_path.clone());
+                }
+
+                // Targets referring to env are resolved by the runner, so they can't be told missing here.
+                if !target_path.exists() && !refers_to_env(&value2.binders[index].target_path) {
+                    discrepancies.push(Discrepancy::MissingTarget {
+                        category: value.name.to_string(),
+                        package: value2.name.to_string(),
+                        target_path,
+                        exec_path,
+                    });
+                    continue;
+                }
+
+                if !inspected_paths.contains(&exec_path) {
+                    inspected_paths.push(exec_path);
+                }
+            }
+        }
+    }
+
+    (discrepancies, exec_paths, inspected_paths)
+}
+
+fn inspect_runner(executor: &RegistrationExecutor, exec_path: &PathBuf) -> Result<Option<Discrepancy>, Vec<CommandError>> {
+    if binder_registered(executor, exec_path)? {
+        return Ok(None);
+    }
+
+    if !exec_path.exists() && !exec_path.is_symlink() {
+        return Ok(Some(Discrepancy::MissingRunner(exec_path.clone())));
+    }
+
+    // Runners are copied on platforms other than Linux, so a different file means an old copy.
+    if cfg!(not(target_os = "linux")) || is_runner_file(executor, exec_path) {
+        return Ok(Some(Discrepancy::OutdatedRunner(exec_path.clone())));
+    }
+
+    Ok(Some(Discrepancy::ForeignFile(exec_path.clone())))
+}
+
+#[cfg(target_os = "linux")]
+fn is_runner_file(executor: &RegistrationExecutor, path: &PathBuf) -> bool {
+    if !path.is_symlink() {
+        return false;
+    }
+
+    match path.read_link() {
+        Ok(value) => value.file_name()
+            .map(| value2 | value2.to_string_lossy().eq(&executor.project_filename))
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_runner_file(executor: &RegistrationExecutor, path: &PathBuf) -> bool {
+    path.is_file() && binder_registered(executor, path).unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn covered_path(path: &PathBuf, exec_paths: &Vec<PathBuf>) -> bool {
+    exec_paths.contains(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn covered_path(path: &PathBuf, exec_paths: &Vec<PathBuf>) -> bool {
+    exec_paths.iter()
+        .any(| value | value.eq(path) || value.parent().map(| value2 | value2.eq(path)).unwrap_or(false))
+}
+
+#[cfg(target_os = "linux")]
+fn owned_path(executor: &RegistrationExecutor, path: &PathBuf) -> bool {
+    // Alternatives are shared with other programs, so only links to runner are ours.
+    is_runner_file(executor, path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn owned_path(_: &RegistrationExecutor, _: &PathBuf) -> bool {
+    true
+}
+
+#[cfg(target_os = "linux")]
+fn registered_dir(path: &PathBuf, _: &Vec<PathBuf>) -> Option<PathBuf> {
+    path.parent().map(| value | value.to_path_buf())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn registered_dir(path: &PathBuf, exec_paths: &Vec<PathBuf>) -> Option<PathBuf> {
+    match exec_paths.contains(path) {
+        true => path.parent().map(| value | value.to_path_buf()),
+        false => Some(path.clone()),
+    }
+}
+
+fn fix_plan(
+    executor: &RegistrationExecutor,
+    config: &RuntimeConfig,
+    discrepancies: &Vec<Discrepancy>,
+    prune: bool,
+) -> Result<RegistrationPlan, Vec<CommandError>> {
+    let mut register_binders: Vec<RegistrationStep> = vec![];
+    let mut register_paths: Vec<RegistrationStep> = vec![];
+    let mut unregister_paths: Vec<RegistrationStep> = vec![];
+    let mut unregister_binders: Vec<RegistrationStep> = vec![];
+    let mut fixed_config = config.clone();
+    let mut pruned_exec_paths: Vec<PathBuf> = vec![];
+    let mut config_changed = false;
+
+    for value in discrepancies.iter() {
+        match value {
+            Discrepancy::MissingRunner(value2) | Discrepancy::OutdatedRunner(value2) => {
+                register_binders.push(RegistrationStep::RegisterBinder(value2.clone()));
+            }
+            Discrepancy::MissingPath(value2) => {
+                register_paths.push(RegistrationStep::RegisterPath(value2.clone()));
+            }
+            Discrepancy::OrphanPath(value2) => {
+                unregister_paths.push(RegistrationStep::UnregisterPath(value2.clone()));
+            }
+            Discrepancy::OrphanRunner(value2) => {
+                unregister_binders.push(RegistrationStep::UnregisterBinder(value2.clone()));
+            }
+            Discrepancy::MissingTarget { .. } if !prune => {}
+            Discrepancy::MissingTarget { category, package, target_path, exec_path } => {
+                for value2 in fixed_config.package_categories.iter_mut() {
+                    if !value2.name.eq(category) {
+                        continue;
+                    }
+
+                    for value3 in value2.packages.iter_mut() {
+                        if !value3.name.eq(package) {
+                            continue;
+                        }
+
+                        // Targets are found missing after references are resolved, while the raw binders are kept.
+                        let interpolated = value3.interpolated().0;
+                        let mut binders = interpolated.binders.iter();
+
+                        value3.binders.retain(| value4 | {
+                            let target_path2 = binders.next()
+                                .map(| value5 | value5.convert_target_to_pathbuf())
+                                .unwrap_or(value4.convert_target_to_pathbuf());
+
+                            !target_path2.eq(target_path) ||
+                                !value4.convert_exec_to_pathbuf().eq(exec_path)
+                        });
+                    }
+                }
+                config_changed = true;
+
+                if !pruned_exec_paths.contains(exec_path) {
+                    pruned_exec_paths.push(exec_path.clone());
+                }
+            }
+            Discrepancy::ForeignFile(_) => {}
+        }
+    }
+
+    // Binders of other packages can share the exec path, so it's unregistered only when nothing uses it anymore.
+    let remaining_exec_paths = collect_exec_paths(&fixed_config.interpolated().0);
+
+    for value in pruned_exec_paths.iter() {
+        if remaining_exec_paths.contains(value) {
+            continue;
+        }
+
+        if path_registered(executor, value)? {
+            unregister_paths.push(RegistrationStep::UnregisterPath(value.clone()));
+        }
+
+        if is_runner_file(executor, value) {
+            unregister_binders.push(RegistrationStep::UnregisterBinder(value.clone()));
+        }
+    }
+
+    let mut plan = RegistrationPlan::default();
+    plan.steps.append(&mut register_binders);
+    plan.steps.append(&mut register_paths);
+    plan.steps.append(&mut unregister_paths);
+    plan.steps.append(&mut unregister_binders);
+
+    if config_changed {
+        fixed_config.package_category_hash = match fixed_config.hash() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't get hash for fixed config.")
+                    ),
+                    CommandError::Others(Box::new(error))
+                ]);
+            }
+        };
+
+        plan.steps.push(RegistrationStep::SaveConfig {
+            config: Box::new(fixed_config),
+            previous: Box::new(config.clone()),
+        });
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process};
+    use pkg_select_shared::common_models::models::configurations::package_category::PackageCategory;
+    use pkg_select_shared::common_models::models::configurations::runnable_package::RunnablePackage;
+    use pkg_select_shared::common_models::models::configurations::target_binder::TargetBinder;
+    use super::*;
+
+    fn package(name: &str, target_path: &PathBuf, execution_path: &str) -> RunnablePackage {
+        RunnablePackage {
+            name: name.to_string(),
+            binders: vec![TargetBinder {
+                target_name: "java".to_string(),
+                target_path: target_path.to_string_lossy().to_string(),
+                execution_path: execution_path.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn config(packages: Vec<RunnablePackage>) -> RuntimeConfig {
+        RuntimeConfig {
+            package_categories: vec![PackageCategory {
+                name: "jdk".to_string(),
+                packages,
+                ..Default::default()
+            }],
+            ..RuntimeConfig::default()
+        }
+    }
+
+    #[test]
+    fn exec_path_shared_by_packages_is_inspected_once() {
+        let directory = std::env::temp_dir().join(format!("pkg-select-doctor-{}", process::id()));
+        fs::create_dir_all(directory.join("temurin-21")).unwrap();
+        fs::create_dir_all(directory.join("zulu-11")).unwrap();
+        fs::write(directory.join("temurin-21/java"), "").unwrap();
+        fs::write(directory.join("zulu-11/java"), "").unwrap();
+
+        let (discrepancies, exec_paths, inspected_paths) = inspect_binders(&config(vec![
+            package("temurin-21", &directory.join("temurin-21"), "/usr/local/bin"),
+            package("zulu-11", &directory.join("zulu-11"), "/usr/local/bin"),
+        ]));
+
+        assert!(discrepancies.is_empty());
+        assert_eq!(exec_paths, vec![PathBuf::from("/usr/local/bin/java")]);
+        assert_eq!(inspected_paths, vec![PathBuf::from("/usr/local/bin/java")]);
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn exec_path_shared_with_missing_target_is_still_inspected() {
+        let directory = std::env::temp_dir().join(format!("pkg-select-doctor-missing-{}", process::id()));
+        fs::create_dir_all(directory.join("zulu-11")).unwrap();
+        fs::write(directory.join("zulu-11/java"), "").unwrap();
+
+        let (discrepancies, exec_paths, inspected_paths) = inspect_binders(&config(vec![
+            package("temurin-21", &directory.join("temurin-21"), "/usr/local/bin"),
+            package("zulu-11", &directory.join("zulu-11"), "/usr/local/bin"),
+        ]));
+
+        assert_eq!(discrepancies.len(), 1);
+        assert!(matches!(&discrepancies[0], Discrepancy::MissingTarget { package, .. } if package == "temurin-21"));
+        assert_eq!(exec_paths, vec![PathBuf::from("/usr/local/bin/java")]);
+        assert_eq!(inspected_paths, vec![PathBuf::from("/usr/local/bin/java")]);
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+use std::ptr;
+use colored::Colorize;
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::configurations::env_var::EnvValue;
+use pkg_select_shared::common_models::models::package_evaluation::{CandidatePackage, PackageEvaluation, SelectionReason, SkipReason};
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::config_resolver::traits::package_search::PackageSearch;
+use pkg_select_shared::{current_working_path, MutationLocker, print_dbg_on_debug, safe_canonicalize};
+use crate::models::commands::evaluate::Evaluate;
+use crate::models::command_option::CommandOption;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+
+impl CLICommand for Evaluate {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["evaluate", "eval"]
+    }
+
+    fn description(&self) -> &str {
+        "Explain which package the runner would pick for the given binary and why."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("<binary-name>")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--cwd"],
+                value: Some("dir"),
+                description: "Evaluate as if the runner is executed in the given directory.",
+            },
+        ]
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let cwd_arg = args.optional_argument(format!("--cwd"));
+        let _binary_name = args.command.get(0)
+            .or(args.non_optional.get(0));
+        let binary_name = match _binary_name {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("binary name is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: evaluate <binary-name> [--cwd=<dir>]")
+                    )
+                ]);
+            }
+            Some(value) => value,
+        };
+
+        print_dbg_on_debug!(&cwd_arg);
+
+        let _working_path = match &cwd_arg {
+            None => current_working_path(),
+            Some(value) => safe_canonicalize(&PathBuf::from(value)),
+        };
+        let working_path = match _working_path {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![CommandError::Others(Box::new(error))]);
+            }
+        };
+
+        // Same as runner, packages are evaluated with their references resolved.
+        let (interpolated, interpolation_errors) = config.value().interpolated();
+
+        let process_paths = find_process_paths(&interpolated, binary_name);
+        if process_paths.is_empty() {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't find binder named {}.", binary_name)
+                )
+            ]);
+        }
+
+        let mut errors: Vec<CommandError> = vec![];
+
+        for value in process_paths.iter() {
+            let mut evaluation = resolver.package_resolver.evaluate_by_paths(
+                &interpolated,
+                value,
+                &working_path,
+            );
+
+            match resolver.package_resolver.find_by_env_override(&interpolated, value) {
+                Ok(None) => {}
+                Ok(Some((value2, applied))) => {
+                    apply_env_override(&mut evaluation, value2, applied);
+                }
+                Err(error) => {
+                    errors.push(CommandError::Others(Box::new(error)));
+                }
+            }
+
+            print_evaluation(value, &working_path, &evaluation);
+        }
+
+        if !errors.is_empty() {
+            errors.push(
+                CommandError::String(
+                    format!("runner would fail with the env above. Unset it or name a package of the category.")
+                )
+            );
+        }
+
+        if !interpolation_errors.is_empty() {
+            for (value, error) in interpolation_errors.iter() {
+                errors.push(
+                    CommandError::String(
+                        format!("{}: {}", value, error.error_type)
+                    )
+                );
+            }
+            errors.push(
+                CommandError::String(
+                    format!("runner would fail since references above couldn't be resolved. Run validate to find every invalid one.")
+                )
+            );
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+fn find_process_paths(config: &RuntimeConfig, binary_name: &String) -> Vec<PathBuf> {
+    if binary_name.contains(std::path::MAIN_SEPARATOR) {
+        return vec![PathBuf::from(binary_name)];
+    }
+
+    let mut paths: Vec<PathBuf> = vec![];
+
+    for value in config.package_categories.iter() {
+        for value2 in value.packages.iter() {
+            for value3 in value2.binders.iter() {
+                let path = value3.convert_exec_to_pathbuf();
+
+                if value3.target_name.eq(binary_name) && !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+fn apply_env_override<'t>(evaluation: &mut PackageEvaluation<'t>, candidate: CandidatePackage<'t>, applied: String) {
+    evaluation.skipped.retain(| (value, _) | !ptr::eq(value.package, candidate.package));
+
+    match evaluation.selected.take() {
+        Some((value, _)) if !ptr::eq(value.package, candidate.package) => {
+            evaluation.skipped.insert(0, (value, SkipReason::Overridden(applied.clone())));
+        }
+        _ => {}
+    }
+
+    evaluation.selected = Some((candidate, SelectionReason::EnvOverride(applied)));
+}
+
+fn print_evaluation(process_path: &PathBuf, working_path: &PathBuf, evaluation: &PackageEvaluation) {
+    println!(
+        "{} {} {} {}",
+        "Evaluated".bright_green(),
+        process_path.to_string_lossy().bold(),
+        "in",
+        working_path.to_string_lossy().bold(),
+    );
+
+    match &evaluation.project_selection {
+        None => {}
+        Some(value) => {
+            println!("  project file: {}", value.path.to_string_lossy());
+        }
+    }
+
+    for value in evaluation.project_selection_errors.iter() {
+        println!("  {} {}", "warning:".yellow(), value.error_type);
+    }
+
+    match &evaluation.selected {
+        None => {
+            println!("{}", "  couldn't find runnable package.".bright_red());
+
+            if !process_path.is_file() {
+                println!("  Hint: {} doesn't exist. Run sync to register it.", process_path.to_string_lossy());
+            }
+        }
+        Some((value, reason)) => {
+            println!("  category: {}", value.category.name);
+            println!("  package: {}", value.package.name.bold());
+            println!("  binder: {}", value.binder.target_name);
+            println!("  target path: {}", value.binder.convert_target_to_pathbuf().to_string_lossy());
+
+            if value.package.envs.is_empty() {
+                println!("  envs: (none)");
+            } else {
+                println!("  envs:");
+
+                let mut envs: Vec<(&String, &EnvValue)> = value.package.envs.iter().collect();
+                envs.sort_by(| value2, value3 | value2.0.cmp(value3.0));
+
+                for (key, value2) in envs.iter() {
+                    println!("    {}={}", key, value2);
+                }
+            }
+
+            println!("  reason: {}", reason.to_string().yellow());
+        }
+    }
+
+    for (value, reason) in evaluation.skipped.iter() {
+        println!(
+            "  skipped: {} ({}) - {}",
+            value.package.name,
+            value.category.name,
+            reason,
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use colored::Colorize;
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::{MutationLocker, project_filename};
+use pkg_select_shared::ProjectType::ProjectSelectCLI;
+use pkg_select_shared::logger::logger::LOG_ENV_KEY;
+use crate::commands;
+use crate::models::commands::help::Help;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+use crate::utils::help::{find_command, format_rows, join_command_path, overview};
+
+impl CLICommand for Help {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["help"]
+    }
+
+    fn description(&self) -> &str {
+        "Print help of every command or the given command."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("[command]")
+    }
+
+    fn main(&self, _: &ConfigResolver, _: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let project_filename = project_filename(ProjectSelectCLI);
+
+        if args.command.is_empty() {
+            println!("{} {}", "Package Select CLI".bold(), env!("CARGO_PKG_VERSION"));
+            println!("{}", overview(&commands(), &project_filename));
+            println!();
+            println!("Global options:");
+            for value in format_rows(&global_option_rows()).iter() {
+                println!("{}", value);
+            }
+
+            return Ok(());
+        }
+
+        let command_path = join_command_path(&project_filename, &args.command.join(" "));
+
+        match find_command(commands(), &args.command) {
+            None => {
+                Err(vec![
+                    CommandError::String(
+                        format!("couldn't find \"{}\" command", args.command.join(" "))
+                    )
+                ])
+            }
+            Some(value) => {
+                println!("{}", value.help(&command_path));
+
+                Ok(())
+            }
+        }
+    }
+}
+
+fn global_option_rows() -> Vec<(String, String)> {
+    vec![
+        (format!("--verbose"), format!("Print debug logs as well.")),
+        (format!("--quiet"), format!("Print error logs only.")),
+        (format!("{}=<level>", LOG_ENV_KEY), format!("Env var to set log level of runners and CLI: off, error, warn, info or debug.")),
+    ]
+}
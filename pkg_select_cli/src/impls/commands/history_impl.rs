@@ -0,0 +1,59 @@
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::config_resolver::traits::config_history::ConfigHistory;
+use pkg_select_shared::MutationLocker;
+use crate::models::commands::history::History;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+use crate::utils::help::format_rows;
+use crate::utils::time::format_timestamp;
+
+impl CLICommand for History {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["history"]
+    }
+
+    fn description(&self) -> &str {
+        "List configs saved before each change, the latest one comes first."
+    }
+
+    fn main(&self, resolver: &ConfigResolver, _: &mut MutationLocker<RuntimeConfig>, _: &Argument) -> Result<(), Vec<CommandError>> {
+        let entries = match resolver.history() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't read config history.")
+                    ),
+                    CommandError::Others(Box::new(error))
+                ]);
+            }
+        };
+
+        if entries.is_empty() {
+            println!("No history found.");
+            return Ok(());
+        }
+
+        let rows: Vec<(String, String)> = entries.iter()
+            .map(| value | {
+                let hash = match value.package_category_hash.is_empty() {
+                    true => format!("(empty)"),
+                    false => value.package_category_hash.chars().take(12).collect(),
+                };
+
+                (format!("{}", value.id), format!("{}    {}", format_timestamp(value.timestamp), hash))
+            })
+            .collect();
+
+        for value in format_rows(&rows).iter() {
+            println!("{}", value);
+        }
+
+        println!();
+        println!("Run \"rollback <id>\" to restore one of them, or \"undo\" to restore the latest one.");
+
+        Ok(())
+    }
+}
@@ -2,5 +2,14 @@ pub mod update_config_impl;
 pub mod validate_impl;
 pub mod sync_impl;
 pub mod desync_impl;
+pub mod evaluate_impl;
+pub mod help_impl;
+pub mod history_impl;
+pub mod rollback_impl;
+pub mod undo_impl;
 pub mod update_subcommands;
 pub mod purge_impl;
+pub mod doctor_impl;
+pub mod use_package_impl;
+pub mod category_impl;
+pub mod discover_impl;
@@ -1,27 +1,76 @@
-use std::io::ErrorKind;
+use std::path::PathBuf;
 use colored::Colorize;
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
-use pkg_select_shared::{current_exec_file_path, MutationLocker, PathPop, project_filename, read_input};
+use pkg_select_shared::{MutationLocker, read_input};
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
-use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
-use pkg_select_shared::ProjectType::ProjectSelectRunner;
-use crate::impls::path_registration::{path_registration_resolver, reset_paths};
-use crate::models::binder_registration_resolver::BinderRegistrationResolver;
+use crate::impls::registration_execution_impl::registration_executor;
 use crate::models::commands::purge::Purge;
-use crate::models::errors::binder_registration_combo::IOBinderRegistrationError;
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
-use crate::traits::binder_registration::BinderRegistration;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
 use crate::traits::cli_command::CLICommand;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::plan::{plan_options, print_plan};
 
 impl CLICommand for Purge {
     fn command_names(&self) -> Vec<&str> {
         vec!["purge"]
     }
 
+    fn description(&self) -> &str {
+        "Erase every package config and registration."
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![
+            CommandOption {
+                names: vec!["--skip-confirm"],
+                value: None,
+                description: "Skip typing confirmation.",
+            },
+        ];
+        options.append(&mut plan_options());
+
+        options
+    }
+
     fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let skip_confirm = &args.optional_flag(format!("--skip-confirm"));
+        let json = args.optional_flag(format!("--json"));
+        let dry_run = json || args.optional_flag(format!("--dry-run"));
+
+        let exec_paths: Vec<PathBuf> = config.value().package_categories.iter()
+            .map(| value | {
+                value.packages.iter()
+                    .map(| value2 | {
+                        value2.binders.iter()
+                            .map(| value3 | value3.convert_exec_to_pathbuf())
+                            .collect::<Vec<PathBuf>>()
+                    })
+                    .collect::<Vec<Vec<PathBuf>>>()
+            })
+            .flatten()
+            .flatten()
+            .collect();
+
+        let mut executor = registration_executor(resolver)?;
+        let mut plan = RegistrationPlan {
+            steps: executor.plan_unregister(&exec_paths)?.into_iter()
+                .filter(| value | matches!(value, RegistrationStep::UnregisterBinder(_)))
+                .collect(),
+        };
+        plan.steps.push(RegistrationStep::ResetConfig {
+            previous: Box::new(config.value().clone()),
+        });
+        plan.steps.push(RegistrationStep::ResetPaths {
+            config: Box::new(config.value().clone()),
+        });
+
+        if dry_run {
+            return print_plan("purge", &executor.describe(&plan), json);
+        }
 
         if !skip_confirm {
             println!("{}", "Every package config data and registration is about to be erased. It can result in occurring unexpected errors.".bright_red());
@@ -34,84 +83,7 @@ impl CLICommand for Purge {
             }
         }
 
-        let process_file_path = match current_exec_file_path() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-        let process_path_without_filename = process_file_path.pop_path();
-        let project_filename = project_filename(ProjectSelectRunner);
-
-        let binder_registration_resolver = BinderRegistrationResolver {};
-        let mut path_registration_resolver = match path_registration_resolver() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-
-        for value in config.value().package_categories.iter() {
-            for value2 in value.packages.iter() {
-                for value3 in value2.binders.iter() {
-                    let registered = match (&binder_registration_resolver).registered(
-                        &value3.convert_exec_to_pathbuf(),
-                        &process_path_without_filename,
-                        &project_filename
-                    ) {
-                        Ok(value) => value,
-                        Err(error) => {
-                            if let IOBinderRegistrationError::IOError(value) = &error {
-                                if value.kind() != ErrorKind::NotFound {
-                                    return Err(vec![CommandError::Others(Box::new(error))]);
-                                }
-                            }
-
-                            false
-                        }
-                    };
-
-                    if registered {
-                        match (&binder_registration_resolver).unregister(
-                            &value3.convert_exec_to_pathbuf()
-                        ) {
-                            Ok(_) => {}
-                            Err(error) => {
-                                return Err(vec![CommandError::Others(Box::new(error))]);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        match reset_paths(
-            &mut path_registration_resolver,
-            config.value(),
-        ) {
-            Ok(_) => {},
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-
-        #[cfg(not(target_os = "linux"))]
-        match reset_paths(
-            &mut path_registration_resolver,
-        ) {
-            Ok(_) => {},
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-
-        match resolver.reset() {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        }
+        executor.execute(&plan)?;
 
         println!("Purge completed.");
 
@@ -0,0 +1,131 @@
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::config_history::ConfigHistoryEntry;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
+use pkg_select_shared::config_resolver::traits::config_history::ConfigHistory;
+use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
+use pkg_select_shared::MutationLocker;
+use crate::impls::commands::update_subcommands::commit_changes_impl::apply_changes;
+use crate::models::commands::rollback::Rollback;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+
+impl CLICommand for Rollback {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["rollback"]
+    }
+
+    fn description(&self) -> &str {
+        "Restore config of the given history entry, then re-sync runners and paths to match it."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("<id>")
+    }
+
+    fn main(&self, resolver: &ConfigResolver, _: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let _id = args.command.get(0)
+            .or(args.non_optional.get(0))
+            .and_then(| value | value.parse::<u64>().ok());
+        let id = match _id {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("history id is undefined or not a number.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: run history to see every id.")
+                    )
+                ]);
+            }
+            Some(value) => value,
+        };
+
+        let entries = match resolver.history() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![CommandError::Others(Box::new(error))]);
+            }
+        };
+        let entry = match entries.iter().find(| value | value.id == id) {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't find history entry {}.", id)
+                    )
+                ]);
+            }
+            Some(value) => value,
+        };
+
+        rollback(resolver, entry)
+    }
+}
+
+/**
+   Save config of the entry as if it was committed in update prompt,
+   so that runners and paths are registered or unregistered by the difference.
+   Its targets are not validated against filesystem, since restoring an older state is the point of it,
+   and the schema is already checked by reading it. Rolling back to the same config does nothing.
+   The current config is saved into history as well, so a rollback can be undone.
+**/
+pub fn rollback(resolver: &ConfigResolver, entry: &ConfigHistoryEntry) -> Result<(), Vec<CommandError>> {
+    let mut target = match resolver.resolve_history(entry) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't read history entry {}.", entry.id)
+                ),
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+    let existed = match resolver.resolve() {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't resolve config.")
+                ),
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+
+    let (existed_hash, target_hash) = match (existed.hash(), target.hash()) {
+        (Ok(value), Ok(value2)) => (value, value2),
+        (Err(error), _) | (_, Err(error)) => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't get hash of config.")
+                ),
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+
+    if existed_hash.eq(&target_hash) {
+        println!("Config is already the same as history entry {}. Nothing to roll back.", entry.id);
+
+        return Ok(());
+    }
+
+    match apply_changes(resolver, existed, &mut target, target_hash, "rollback", false, false) {
+        Ok(_) => {}
+        Err(mut error) => {
+            error.insert(
+                0,
+                CommandError::String(
+                    format!("couldn't roll back to history entry {}.", entry.id)
+                )
+            );
+            return Err(error);
+        }
+    }
+
+    println!("Rolled back to history entry {}.", entry.id);
+
+    Ok(())
+}
@@ -1,181 +1,93 @@
-use std::io::ErrorKind;
+use std::path::PathBuf;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
-use pkg_select_shared::{current_exec_file_path, MutationLocker, PathPop, print_dbg_on_debug, project_filename};
+use pkg_select_shared::{MutationLocker, print_dbg_on_debug};
 use pkg_select_shared::argument_parser::models::argument::Argument;
-use pkg_select_shared::common_models::models::configurations::target_binder::TargetBinder;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
-use pkg_select_shared::ProjectType::ProjectSelectRunner;
-use crate::impls::path_registration::{filter_paths_unregistered, path_registration_resolver, register_paths};
-use crate::models::binder_registration_resolver::BinderRegistrationResolver;
+use crate::impls::registration_execution_impl::registration_executor;
 use crate::models::commands::sync::Sync;
-use crate::models::errors::binder_registration_combo::IOBinderRegistrationError;
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
-use crate::traits::binder_registration::BinderRegistration;
+use crate::models::registration_plan::RegistrationPlan;
 use crate::traits::cli_command::CLICommand;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::plan::{plan_options, print_plan};
 
 impl CLICommand for Sync {
     fn command_names(&self) -> Vec<&str> {
         vec!["sync"]
     }
 
-    fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+    fn description(&self) -> &str {
+        "Register runners and paths of every binder in config."
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![
+            CommandOption {
+                names: vec!["--target"],
+                value: Some("category"),
+                description: "Register binders of the given category only.",
+            },
+        ];
+        options.append(&mut plan_options());
+
+        options
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let target_arg = args.optional_argument(format!("--target"));
+        let json = args.optional_flag(format!("--json"));
+        let dry_run = json || args.optional_flag(format!("--dry-run"));
 
         print_dbg_on_debug!(&target_arg);
 
-        let process_file_path = match current_exec_file_path() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-        let process_path_without_filename = process_file_path.pop_path();
-        let project_filename = project_filename(ProjectSelectRunner);
-
-        let binder_registration_resolver = BinderRegistrationResolver {};
-        let mut path_registration_resolver = match path_registration_resolver() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
+        let mut exec_paths: Vec<PathBuf> = vec![];
 
-        match &target_arg {
-            None => {
-                let mut binders: Vec<TargetBinder> = vec![];
-
-                for value in config.value().package_categories.iter() {
-                    for value2 in value.packages.iter() {
-                        for value3 in value2.binders.iter() {
-                            let registered = match (&binder_registration_resolver).registered(
-                                &value3.convert_exec_to_pathbuf(),
-                                &process_path_without_filename,
-                                &project_filename
-                            ) {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    if let IOBinderRegistrationError::IOError(value) = &error {
-                                        if value.kind() != ErrorKind::NotFound {
-                                            return Err(vec![CommandError::Others(Box::new(error))]);
-                                        }
-                                    }
-
-                                    false
-                                }
-                            };
-
-                            if !registered {
-                                match (&binder_registration_resolver).register(
-                                    &value3.convert_exec_to_pathbuf(),
-                                    &process_path_without_filename,
-                                    &project_filename
-                                ) {
-                                    Ok(_) => {}
-                                    Err(error) => {
-                                        return Err(vec![CommandError::Others(Box::new(error))]);
-                                    }
-                                }
-                            }
-
-                            binders.push(value3.clone());
-                        }
+        for value in config.value().package_categories.iter() {
+            match &target_arg {
+                None => {}
+                Some(value2) => {
+                    if !value.name.eq(value2) {
+                        continue;
                     }
                 }
+            }
 
-                let unregistered_binders = match filter_paths_unregistered(
-                    &mut path_registration_resolver,
-                    &binders,
-                ) {
-                    Ok(value) => value,
-                    Err(error) => {
-                        return Err(vec![CommandError::Others(Box::new(error))]);
-                    }
-                };
-
-                match register_paths(
-                    &mut path_registration_resolver,
-                    &unregistered_binders,
-                ) {
-                    Ok(_) => {}
-                    Err(error) => {
-                        return Err(vec![CommandError::Others(Box::new(error))]);
-                    }
+            for value2 in value.packages.iter() {
+                for value3 in value2.binders.iter() {
+                    exec_paths.push(value3.convert_exec_to_pathbuf());
                 }
             }
-            Some(value) => {
-                let mut binders: Vec<TargetBinder> = vec![];
-                let _category = config.value().package_categories.iter()
-                    .find(| value2 | value2.name.eq(&value.to_string()));
-                let category = match _category {
-                    None => {
-                        return Err(vec![
-                            CommandError::String(
-                                format!("No found package named {}", value)
-                            )
-                        ]);
-                    }
-                    Some(value) => value
-                };
-
-                for value in category.packages.iter() {
-                    for value2 in value.binders.iter() {
-                        let registered = match (&binder_registration_resolver).registered(
-                            &value2.convert_exec_to_pathbuf(),
-                            &process_path_without_filename,
-                            &project_filename
-                        ) {
-                            Ok(value) => value,
-                            Err(error) => {
-                                if let IOBinderRegistrationError::IOError(value) = &error {
-                                    if value.kind() != ErrorKind::NotFound {
-                                        return Err(vec![CommandError::Others(Box::new(error))]);
-                                    }
-                                }
-
-                                false
-                            }
-                        };
-
-                        if !registered {
-                            match (&binder_registration_resolver).register(
-                                &value2.convert_exec_to_pathbuf(),
-                                &process_path_without_filename,
-                                &project_filename
-                            ) {
-                                Ok(_) => {}
-                                Err(error) => {
-                                    return Err(vec![CommandError::Others(Box::new(error))]);
-                                }
-                            }
-                        }
-
-                        binders.push(value2.clone());
-                    }
-                }
+        }
 
-                let unregistered_binders = match filter_paths_unregistered(
-                    &mut path_registration_resolver,
-                    &binders,
-                ) {
-                    Ok(value) => value,
-                    Err(error) => {
-                        return Err(vec![CommandError::Others(Box::new(error))]);
-                    }
-                };
-
-                match register_paths(
-                    &mut path_registration_resolver,
-                    &unregistered_binders,
-                ) {
-                    Ok(_) => {}
-                    Err(error) => {
-                        return Err(vec![CommandError::Others(Box::new(error))]);
-                    }
+        match &target_arg {
+            None => {}
+            Some(value) => {
+                let found = config.value().package_categories.iter()
+                    .any(| value2 | value2.name.eq(value));
+
+                if !found {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("No found package named {}", value)
+                        )
+                    ]);
                 }
             }
         }
 
+        let mut executor = registration_executor(resolver)?;
+        let plan = RegistrationPlan {
+            steps: executor.plan_register(&exec_paths)?,
+        };
+
+        if dry_run {
+            return print_plan("sync", &executor.describe(&plan), json);
+        }
+
+        executor.execute(&plan)?;
+
         println!("Synced successfully!");
 
         Ok(())
@@ -0,0 +1,39 @@
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::config_resolver::traits::config_history::ConfigHistory;
+use pkg_select_shared::MutationLocker;
+use crate::impls::commands::rollback_impl::rollback;
+use crate::models::commands::undo::Undo;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+
+impl CLICommand for Undo {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["undo"]
+    }
+
+    fn description(&self) -> &str {
+        "Roll back the last change. Running it again redoes the change."
+    }
+
+    fn main(&self, resolver: &ConfigResolver, _: &mut MutationLocker<RuntimeConfig>, _: &Argument) -> Result<(), Vec<CommandError>> {
+        let entries = match resolver.history() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![CommandError::Others(Box::new(error))]);
+            }
+        };
+
+        match entries.first() {
+            None => {
+                Err(vec![
+                    CommandError::String(
+                        format!("nothing to undo.")
+                    )
+                ])
+            }
+            Some(value) => rollback(resolver, value),
+        }
+    }
+}
@@ -1,14 +1,18 @@
+use std::fs;
 use std::rc::Rc;
 use struct_indexer_core::ToNamedRcStruct;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
-use pkg_select_shared::{MutationLocker, pause_command_line, read_input};
+use pkg_select_shared::{MutationLocker, pause_command_line, print_dbg_on_debug, read_input};
 use pkg_select_shared::argument_parser::argument_parser::parse_args;
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use crate::models::command_resolver::CommandResolver;
+use crate::models::command_option::CommandOption;
 use crate::models::commands::update_config::UpdateConfig;
 use crate::models::commands::update_subcommands::commit_changes::CommitChanges;
 use crate::models::commands::update_subcommands::env_var::EnvVar;
+use crate::models::commands::update_subcommands::help::Help;
+use crate::models::commands::discover::Discover;
 use crate::models::commands::update_subcommands::package_category::PackageCategory;
 use crate::models::commands::update_subcommands::runnable_package::RunnablePackage;
 use crate::models::commands::update_subcommands::target_binder::TargetBinder;
@@ -18,18 +22,59 @@ use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
 use crate::traits::command_resolve::CommandResolve;
 use crate::traits::command_search::CommandSearch;
+use crate::utils::plan::plan_options;
+use crate::utils::validation::strict_option;
 
 impl CLICommand for UpdateConfig {
     fn command_names(&self) -> Vec<&str> {
         vec!["update", "update-config"]
     }
 
-    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, _: &Argument) -> Result<(), Vec<CommandError>> {
+    fn description(&self) -> &str {
+        "Open update prompt to edit config, or run one of its commands such as \"package update\" and commit without the prompt."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("[<command> [options] [-- <values> ...]]")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![
+            CommandOption {
+                names: vec!["--file"],
+                value: Some("script"),
+                description: "Run every line of the script as in the prompt, then commit them at once. Nothing is saved if a line fails.",
+            },
+            CommandOption {
+                names: vec!["--commit"],
+                value: None,
+                description: "Commit after running the given command or script. It is implied, so it can't be used with the prompt.",
+            },
+            strict_option(),
+        ];
+        options.append(&mut plan_options());
+
+        options
+    }
+
+    fn subcommands(&self) -> Vec<CommandOrCollection> {
+        vec![
+            CommandOrCollection::Command(Rc::new(CommitChanges {})),
+            CommandOrCollection::Collection(Rc::new(PackageCategory {})),
+            CommandOrCollection::Collection(Rc::new(RunnablePackage {})),
+            CommandOrCollection::Collection(Rc::new(EnvVar {})),
+            CommandOrCollection::Collection(Rc::new(TargetBinder {})),
+            CommandOrCollection::Collection(Rc::new(Discover {})),
+            CommandOrCollection::Command(Rc::new(Help {})),
+        ]
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let mut copied = config.value().clone();
         let mut config_mutation_locker = MutationLocker::create(&mut copied, true);
 
         let mut command_resolver = CommandResolver::default();
-        match resolve_commands(&mut command_resolver) {
+        match resolve_commands(&mut command_resolver, self.subcommands()) {
             Ok(_) => {}
             Err(error) => {
                 return Err(vec![
@@ -41,11 +86,126 @@ impl CLICommand for UpdateConfig {
             }
         };
 
-        match run_command_by_input(resolver, &command_resolver, &mut config_mutation_locker) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(vec![]),
+        match args.optional_argument(format!("--file")) {
+            Some(value) => {
+                match run_commands_by_file(resolver, &command_resolver, &mut config_mutation_locker, &value) {
+                    Ok(_) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+            None if !args.command.is_empty() => {
+                // Options of commit belong to the implied commit, not to the command.
+                let mut command_args = args.clone();
+                for value in ["--commit", "--strict", "--dry-run", "--json"] {
+                    command_args.remove_optional(value.to_string());
+                }
+
+                match run_command_by_args(resolver, &command_resolver, &mut config_mutation_locker, command_args) {
+                    Ok(_) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+            None if args.optional_flag(format!("--commit")) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("--commit needs a command or --file. Changes in the prompt are saved by commit.")
+                    ),
+                ]);
+            }
+            None => {
+                return match run_command_by_input(resolver, &command_resolver, &mut config_mutation_locker) {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(vec![]),
+                };
+            }
+        }
+
+        // Options of commit such as --dry-run and --strict are given to update, since commit is implied.
+        (CommitChanges {}).main(resolver, &mut config_mutation_locker, args)
+    }
+}
+
+/**
+   Run every line in the script against the copied config, answering yes to confirmations of commands.
+   Empty lines, lines starting with "#" and commit lines are skipped, because the caller commits once at the end.
+**/
+fn run_commands_by_file(
+    config_resolver: &ConfigResolver,
+    command_resolver: &CommandResolver,
+    config: &mut MutationLocker<RuntimeConfig>,
+    path: &String,
+) -> Result<(), Vec<CommandError>> {
+    let script = match fs::read_to_string(path) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't read script {}.", path)
+                ),
+                CommandError::Others(Box::new(error)),
+            ]);
+        }
+    };
+
+    for (index, value) in script.lines().enumerate() {
+        let line = value.trim();
+        if line.is_empty() || line.starts_with("#") {
+            continue;
+        }
+
+        // Nobody answers prompts of commands while the script runs.
+        let mut args = parse_args(line.to_string());
+        args.set_optional(format!("--yes"), None);
+
+        match run_command_by_args(config_resolver, command_resolver, config, args) {
+            Ok(_) => {}
+            Err(mut error) => {
+                error.insert(
+                    0,
+                    CommandError::String(
+                        format!("{}:{}: failed running \"{}\". Nothing is saved.", path, index + 1, line)
+                    )
+                );
+                return Err(error);
+            }
         }
     }
+
+    Ok(())
+}
+
+/**
+   Run a command without the prompt. Commit command is skipped, because the caller commits after it.
+**/
+fn run_command_by_args(
+    config_resolver: &ConfigResolver,
+    command_resolver: &CommandResolver,
+    config: &mut MutationLocker<RuntimeConfig>,
+    mut args: Argument,
+) -> Result<(), Vec<CommandError>> {
+    let (command, command_length) = match command_resolver.find_by_command(&args.command) {
+        None => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't find \"{}\" command in update.", &args.command.join(" "))
+                ),
+                CommandError::String(
+                    format!("Hint: run help update to see every command.")
+                ),
+            ]);
+        }
+        Some(value) => value,
+    };
+    args.command.drain(..command_length);
+
+    match Rc::clone(command).to_named_rc_struct::<CommitChanges>() {
+        Ok(_) => {
+            return Ok(());
+        }
+        Err(_) => {}
+    }
+
+    command.main(config_resolver, config, &args)
 }
 
 fn run_command_by_input(
@@ -56,12 +216,12 @@ fn run_command_by_input(
     print!("> ");
     let input = read_input().expect("couldn't get input from terminal.");
 
-    let args = parse_args((&input).replace("\n", ""));
-    dbg!(&args);
-    let command_name = (&args).command.join("/");
-    let command = match command_resolver.find_by_name(&command_name) {
+    let mut args = parse_args((&input).replace("\n", ""));
+    print_dbg_on_debug!(&args);
+    let (command, command_length) = match command_resolver.find_by_command(&args.command) {
         None => {
-            println!("couldn't find \"{}\" command", &command_name.replace("/", " "));
+            println!("couldn't find \"{}\" command", &args.command.join(" "));
+            println!("Hint: type help to see every command.");
 
             pause_command_line().expect("couldn't pause.");
 
@@ -69,6 +229,14 @@ fn run_command_by_input(
         }
         Some(value) => value,
     };
+    let command_name = (&args).command[..command_length].join("/");
+    args.command.drain(..command_length);
+
+    if args.optional_flag(format!("--help")) {
+        println!("{}", command.help(&command_name.replace("/", " ")));
+
+        return run_command_by_input(config_resolver, command_resolver, config);
+    }
 
     return match command.run(&config_resolver, config, &args) {
         Ok(_) => {
@@ -89,10 +257,10 @@ fn run_command_by_input(
     }
 }
 
-fn resolve_commands(resolver: &mut CommandResolver) -> Result<(), CommandResolveError> {
-    resolver.resolve(CommandOrCollection::Command(Rc::new(CommitChanges {})))?;
-    resolver.resolve(CommandOrCollection::Collection(Rc::new(PackageCategory {})))?;
-    resolver.resolve(CommandOrCollection::Collection(Rc::new(RunnablePackage {})))?;
-    resolver.resolve(CommandOrCollection::Collection(Rc::new(EnvVar {})))?;
-    resolver.resolve(CommandOrCollection::Collection(Rc::new(TargetBinder {})))
+fn resolve_commands(resolver: &mut CommandResolver, commands: Vec<CommandOrCollection>) -> Result<(), CommandResolveError> {
+    for value in commands.into_iter() {
+        resolver.resolve(value)?;
+    }
+
+    Ok(())
 }
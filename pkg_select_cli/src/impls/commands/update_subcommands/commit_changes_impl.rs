@@ -3,24 +3,41 @@ use pkg_select_shared::argument_parser::models::argument::Argument;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
 use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
+use pkg_select_shared::common_models::traits::validator::Validator;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
-use pkg_select_shared::{current_exec_file_path, MutationLocker, PathPop, print_dbg_on_debug, project_filename};
-use pkg_select_shared::ProjectType::ProjectSelectRunner;
-use crate::impls::path_registration::{check_raw_path_registered, path_registration_resolver, register_raw_paths, unregister_raw_paths};
-use crate::models::binder_registration_resolver::BinderRegistrationResolver;
+use pkg_select_shared::{MutationLocker, print_dbg_on_debug};
+use crate::impls::registration_execution_impl::registration_executor;
 use crate::models::commands::update_subcommands::commit_changes::CommitChanges;
-use crate::models::commands::validate::Validate;
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
-use crate::traits::binder_registration::BinderRegistration;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
 use crate::traits::cli_command::CLICommand;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::plan::{plan_options, print_plan};
+use crate::utils::validation::{collect_report, strict_option};
 
 impl CLICommand for CommitChanges {
     fn command_names(&self) -> Vec<&str> {
         vec!["commit", "commit-changes"]
     }
 
-    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, _: &Argument) -> Result<(), Vec<CommandError>> {
+    fn description(&self) -> &str {
+        "Validate changes, then register or unregister runners and paths of changed binders and save them as a single reversible plan."
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        let mut options = vec![strict_option()];
+        options.append(&mut plan_options());
+
+        options
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let json = args.optional_flag(format!("--json"));
+        let dry_run = json || args.optional_flag(format!("--dry-run"));
+        let strict = args.optional_flag(format!("--strict"));
+
         let existed = match resolver.resolve() {
             Ok(value) => value,
             Err(error) => {
@@ -73,219 +90,106 @@ impl CLICommand for CommitChanges {
             ]);
         }
 
-        dbg!(&config_mut);
+        print_dbg_on_debug!(&config_mut);
 
         if config_mut.package_categories.len() > 0 {
-            let validator = Validate {};
-            let mut validator_mutation_locker = MutationLocker::create(config_mut, true);
-            let mut validator_args = Argument::default();
-            validator_args.set_optional(format!("--skip-registration"), None);
-            match validator.main(resolver, &mut validator_mutation_locker, &validator_args) {
-                Ok(_) => false,
-                Err(mut error) => {
-                    error.insert(
-                        0,
-                        CommandError::String(
-                            format!("failed validation before saving...")
-                        )
-                    );
-                    return Err(error);
-                }
-            };
-        }
+            let mut errors: Vec<CommandError> = vec![];
+            collect_report(config_mut.validate(), strict, &mut errors)?;
 
-        let process_file_path = match current_exec_file_path() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![CommandError::Others(Box::new(error))]);
-            }
-        };
-        let process_path_without_filename = process_file_path.pop_path();
-        let project_filename = project_filename(ProjectSelectRunner);
+            if !errors.is_empty() {
+                errors.insert(
+                    0,
+                    CommandError::String(
+                        format!("failed validation before saving...")
+                    )
+                );
 
-        let binder_registration_resolver = BinderRegistrationResolver {};
-        let mut path_registration_resolver = match path_registration_resolver() {
-            Ok(value) => value,
-            Err(error) => {
-                return Err(vec![
-                    CommandError::Others(Box::new(error))
-                ]);
+                return Err(errors);
             }
-        };
-
-        let mut existed_info: Vec<PathBuf> = (&existed).package_categories.iter()
-            .map(| value | {
-                value.packages.iter()
-                    .map(| value2 | {
-                        value2.binders.iter()
-                            .map(| value3 | value3.convert_exec_to_pathbuf())
-                            .collect::<Vec<PathBuf>>()
-                    })
-                    .collect::<Vec<Vec<PathBuf>>>()
-            })
-            .flatten()
-            .flatten()
-            .collect();
-
-        let updated_info: Vec<PathBuf> = (&config_mut).package_categories.iter()
-            .map(| value | {
-                value.packages.iter()
-                    .map(| value2 | {
-                        value2.binders.iter()
-                            .map(| value3 | value3.convert_exec_to_pathbuf())
-                            .collect::<Vec<PathBuf>>()
-                    })
-                    .collect::<Vec<Vec<PathBuf>>>()
-            })
-            .flatten()
-            .flatten()
-            .collect();
-
-        let mut __sync_targets: Vec<&PathBuf> = updated_info.iter()
-            .map(| value | {
-                if existed_info.len() == 0 {
-                    return Some(value);
-                }
-
-                let found_existed_index = match existed_info.iter()
-                    .position(| value2 | value.eq(value2)) {
-                    None => {
-                        return Some(value);
-                    },
-                    Some(value) => value,
-                };
-
-                let found_path = existed_info.remove(found_existed_index);
-                let binder_registered = match binder_registration_resolver.registered(
-                    &found_path,
-                    &process_path_without_filename,
-                    &project_filename,
-                ) {
-                    Ok(value) => value,
-                    Err(_) => false,
-                };
-                if !binder_registered {
-                    return Some(value);
-                }
-
-
-                let path_registered = match check_raw_path_registered(&path_registration_resolver, value) {
-                    Ok(value) => value,
-                    Err(_) => false,
-                };
-                if !path_registered {
-                    return Some(value);
-                }
-
-                None
-            })
-            .filter(| value | value.is_some())
-            .map(| value | value.unwrap())
-            .collect();
-
-        let _sync_targets: Result<Vec<&PathBuf>, Vec<CommandError>> = __sync_targets.iter()
-            .map(| value | {
-                let binder_registered = match binder_registration_resolver.registered(
-                    value,
-                    &process_path_without_filename,
-                    &project_filename,
-                ) {
-                    Ok(value2) => value2,
-                    Err(error) => {
-                        return Err(vec![
-                            CommandError::Others(Box::new(error))
-                        ]);
-                    }
-                };
-
-                if !binder_registered {
-                    match binder_registration_resolver.register(
-                        value,
-                        &process_path_without_filename,
-                        &project_filename,
-                    ) {
-                        Ok(_) => {},
-                        Err(error) => {
-                            return Err(vec![
-                                CommandError::Others(Box::new(error))
-                            ]);
-                        }
-                    }
-                }
-
-                let path_registered = match check_raw_path_registered(&path_registration_resolver, value) {
-                    Ok(value2) => value2,
-                    Err(error) => {
-                        return Err(vec![
-                            CommandError::Others(Box::new(error))
-                        ]);
-                    }
-                };
-
-                if !path_registered {
-                    return Ok(*value);
-                }
-
-                Ok(*value)
-            })
-            .collect();
+        }
 
-        let sync_targets = match _sync_targets {
+        let steps = match apply_changes(resolver, existed, config_mut, changed_hash, "commit", dry_run, json) {
             Ok(value) => value,
-            Err(error) => {
+            Err(mut error) => {
+                error.insert(
+                    0,
+                    CommandError::String(
+                        format!("couldn't commit changes.")
+                    )
+                );
                 return Err(error);
             }
         };
 
-        match register_raw_paths(&mut path_registration_resolver, &sync_targets) {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(vec![
-                    CommandError::Others(Box::new(error))
-                ]);
-            }
-        }
-
-        let desync_targets: Vec<PathBuf> = existed_info;
-
-        let desync_target_refs: Vec<&PathBuf> = desync_targets.iter().collect();
-
-        match unregister_raw_paths(
-            &mut path_registration_resolver,
-            &desync_target_refs
-        ) {
-            Ok(_) => {},
-            Err(error) => {
-                print_dbg_on_debug!(&error);
-            }
-        }
-
-        for value in (&desync_targets).iter() {
-            match binder_registration_resolver.unregister(
-                value,
-            ) {
-                Ok(_) => {},
-                Err(error) => {
-                    print_dbg_on_debug!(&error);
-                }
+        match steps {
+            None => {}
+            Some(value) => {
+                println!("Saved changes successfully! ({} steps applied)", value);
             }
         }
 
-        config_mut.package_category_hash = changed_hash;
+        Ok(())
+    }
+}
 
-        dbg!(&config_mut);
+/**
+   Register runners and paths of changed config, unregister the ones only existed config has,
+   and save changed config as a single reversible plan.
+   Returns the number of applied steps, or nothing if it is a dry run and the plan is printed instead.
+**/
+pub(crate) fn apply_changes(
+    resolver: &ConfigResolver,
+    existed: RuntimeConfig,
+    changed: &mut RuntimeConfig,
+    changed_hash: String,
+    plan_name: &str,
+    dry_run: bool,
+    json: bool,
+) -> Result<Option<usize>, Vec<CommandError>> {
+    let mut executor = registration_executor(resolver)?;
+
+    let existed_info: Vec<PathBuf> = collect_exec_paths(&existed);
+    let updated_info: Vec<PathBuf> = collect_exec_paths(changed);
+
+    let desync_targets: Vec<PathBuf> = existed_info.iter()
+        .filter(| value | !updated_info.contains(value))
+        .map(| value | value.clone())
+        .collect();
+
+    let mut plan = RegistrationPlan::default();
+    plan.steps.append(&mut executor.plan_register(&updated_info)?);
+    plan.steps.append(&mut executor.plan_unregister(&desync_targets)?);
+
+    changed.package_category_hash = changed_hash;
+
+    print_dbg_on_debug!(&changed);
+
+    plan.steps.push(RegistrationStep::SaveConfig {
+        config: Box::new(changed.clone()),
+        previous: Box::new(existed),
+    });
+
+    if dry_run {
+        return print_plan(plan_name, &executor.describe(&plan), json).map(| _ | None);
+    }
 
-        match resolver.update(config_mut) {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(vec![
-                   CommandError::Others(Box::new(error))
-                ]);
-            }
-        }
+    executor.execute(&plan)?;
 
-        println!("Saved changes successfully!");
+    Ok(Some(plan.steps.len()))
+}
 
-        Ok(())
-    }
+pub(crate) fn collect_exec_paths(config: &RuntimeConfig) -> Vec<PathBuf> {
+    config.package_categories.iter()
+        .map(| value | {
+            value.packages.iter()
+                .map(| value2 | {
+                    value2.binders.iter()
+                        .map(| value3 | value3.convert_exec_to_pathbuf())
+                        .collect::<Vec<PathBuf>>()
+                })
+                .collect::<Vec<Vec<PathBuf>>>()
+        })
+        .flatten()
+        .flatten()
+        .collect()
 }
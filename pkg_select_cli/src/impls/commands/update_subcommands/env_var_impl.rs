@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::rc::Rc;
+use pkg_select_shared::common_models::models::configurations::env_var::{EnvOperation, EnvValue};
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::MutationLocker;
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use crate::models::commands::update_subcommands::env_var::{EnvVar, EnvVarDelete, EnvVarUpdate};
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
 use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
@@ -15,6 +17,10 @@ impl CommandCollection for EnvVar {
         vec!["env", "env-var"]
     }
 
+    fn description(&self) -> &str {
+        "Manage env vars injected into a package."
+    }
+
     fn commands(&self) -> Vec<CommandOrCollection> {
         vec![
             CommandOrCollection::Command(Rc::new(EnvVarUpdate {})),
@@ -28,6 +34,39 @@ impl CLICommand for EnvVarUpdate {
         vec!["update", "up"]
     }
 
+    fn description(&self) -> &str {
+        "Add or update env vars injected into the package."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <key>=<value> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--package-name", "--package"],
+                value: Some("name"),
+                description: "Name of package which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--operation", "--op"],
+                value: Some("set|prepend|append|set-if-unset|unset"),
+                description: "How the vars are applied to the inherited ones. Defaults to set.",
+            },
+            CommandOption {
+                names: vec!["--separator"],
+                value: Some("separator"),
+                description: "Separator for prepend and append. Defaults to the path list separator of the platform.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -72,12 +111,38 @@ impl CLICommand for EnvVarUpdate {
             Some(value) => value,
         };
 
-        let vars: HashMap<String, String> = args.non_optional.iter()
-            .filter(| value | value.contains("="))
-            .map(| value | {
-                let split: Vec<&str> = value.split("=").collect();
+        let _operation = args.optional_argument(format!("--operation"))
+            .or(args.optional_argument(format!("--op")));
+        let operation = match _operation {
+            None => EnvOperation::Set,
+            Some(value) => match EnvOperation::from_name(&value) {
+                None => {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("unknown env operation {}.", value)
+                        ),
+                        CommandError::String(
+                            format!("Hint: use one of {}", EnvOperation::all().iter()
+                                .map(| value2 | value2.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", "))
+                        )
+                    ])
+                }
+                Some(value2) => value2,
+            },
+        };
+        let separator = args.optional_argument(format!("--separator"));
 
-                (split[0].to_string(), split[1].to_string())
+        // Values may contain "=" like JAVA_TOOL_OPTIONS, and unset takes keys only.
+        let vars: HashMap<String, EnvValue> = args.non_optional.iter()
+            .filter_map(| value | match value.split_once("=") {
+                Some((key, value2)) => Some((key.to_string(), value2.to_string())),
+                None if operation == EnvOperation::Unset => Some((value.to_string(), format!(""))),
+                None => None,
+            })
+            .map(| (key, value) | {
+                (key, EnvValue { operation, value, separator: separator.clone() })
             })
             .collect();
 
@@ -96,7 +161,7 @@ impl CLICommand for EnvVarUpdate {
     }
 }
 
-fn update(category_name: &String, package_name: &String, values: &HashMap<String, String>, config: &mut RuntimeConfig) -> Result<(), Vec<CommandError>> {
+fn update(category_name: &String, package_name: &String, values: &HashMap<String, EnvValue>, config: &mut RuntimeConfig) -> Result<(), Vec<CommandError>> {
     let _category = config.package_categories.iter_mut()
         .find(| value | value.name.eq(category_name));
     let category = match _category {
@@ -124,14 +189,14 @@ fn update(category_name: &String, package_name: &String, values: &HashMap<String
     };
 
     for (key, value) in values.iter() {
-        let existed = package.envs.insert(key.to_string(), value.to_string());
+        let existed = package.envs.insert(key.to_string(), value.clone());
 
         match existed {
             None => {
-                println!("Added env var {} into runnable package {}.", key, package.name);
+                println!("Added env var {} ({}) into runnable package {}.", key, value.operation, package.name);
             }
             Some(_) => {
-                println!("Updated env var {} into runnable package {}.", key, package.name);
+                println!("Updated env var {} ({}) into runnable package {}.", key, value.operation, package.name);
             }
         }
     }
@@ -144,6 +209,29 @@ impl CLICommand for EnvVarDelete {
         vec!["delete", "del"]
     }
 
+    fn description(&self) -> &str {
+        "Remove env vars from the package."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <key> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--package-name", "--package"],
+                value: Some("name"),
+                description: "Name of package which the targets belong to.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -0,0 +1,48 @@
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::MutationLocker;
+use crate::models::commands::update_config::UpdateConfig;
+use crate::models::commands::update_subcommands::help::Help;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+use crate::utils::help::{find_command, overview};
+
+impl CLICommand for Help {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["help"]
+    }
+
+    fn description(&self) -> &str {
+        "Print help of every command in update prompt or the given command."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("[command]")
+    }
+
+    fn main(&self, _: &ConfigResolver, _: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let commands = UpdateConfig {}.subcommands();
+
+        if args.command.is_empty() {
+            println!("{}", overview(&commands, ""));
+
+            return Ok(());
+        }
+
+        match find_command(commands, &args.command) {
+            None => {
+                Err(vec![
+                    CommandError::String(
+                        format!("couldn't find \"{}\" command", args.command.join(" "))
+                    )
+                ])
+            }
+            Some(value) => {
+                println!("{}", value.help(&args.command.join(" ")));
+
+                Ok(())
+            }
+        }
+    }
+}
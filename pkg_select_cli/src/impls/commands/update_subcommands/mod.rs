@@ -1,4 +1,5 @@
 pub mod commit_changes_impl;
+pub mod help_impl;
 pub mod package_category_impl;
 pub mod runnable_package_impl;
 pub mod env_var_impl;
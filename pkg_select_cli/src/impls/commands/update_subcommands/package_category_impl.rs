@@ -2,23 +2,35 @@ use std::rc::Rc;
 use pkg_select_shared::common_models::models::configurations::package_category::PackageCategory;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
+use pkg_select_shared::common_models::traits::validator::Validator;
 use pkg_select_shared::{MutationLocker, read_input};
 use pkg_select_shared::argument_parser::models::argument::Argument;
-use crate::models::commands::update_subcommands::package_category::{PackageCategory as PackageCategoryCommand, PackageCategoryDelete, PackageCategoryUpdate};
+use crate::models::commands::update_subcommands::package_category::{PackageCategory as PackageCategoryCommand, PackageCategoryDelete, PackageCategorySetDefault, PackageCategoryUpdate};
+use crate::impls::registration_execution_impl::registration_executor;
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
 use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
 use crate::traits::command_collection::CommandCollection;
+use crate::traits::registration_execution::RegistrationExecution;
+use crate::utils::validation::collect_report;
 
 impl CommandCollection for PackageCategoryCommand {
     fn collection_names(&self) -> Vec<&str> {
         vec!["category", "package-category"]
     }
 
+    fn description(&self) -> &str {
+        "Manage package categories such as jdk."
+    }
+
     fn commands(&self) -> Vec<CommandOrCollection> {
         vec![
             CommandOrCollection::Command(Rc::new(PackageCategoryUpdate {})),
             CommandOrCollection::Command(Rc::new(PackageCategoryDelete {})),
+            CommandOrCollection::Command(Rc::new(PackageCategorySetDefault {})),
         ]
     }
 }
@@ -28,6 +40,14 @@ impl CLICommand for PackageCategoryUpdate {
         vec!["update", "up"]
     }
 
+    fn description(&self) -> &str {
+        "Add package categories or replace existing ones."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <category-name> ...")
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -107,7 +127,7 @@ fn update(values: &Vec<PackageCategory>, config: &mut RuntimeConfig) -> () {
                 print!("> ");
                 let input = read_input().expect("couldn't get input from terminal.").to_lowercase().replace("\n", "");
                 if ((&input).len() == 1 && (&input).contains("y")) || (&input).eq(&format!("yes")) {
-                    let default_package = config.package_categories[index].default_package;
+                    let default_package = config.package_categories[index].default_package.clone();
                     let packages = config.package_categories[index].packages.clone();
 
                     config.package_categories[index] = value.clone();
@@ -128,6 +148,14 @@ impl CLICommand for PackageCategoryDelete {
         vec!["delete", "del"]
     }
 
+    fn description(&self) -> &str {
+        "Remove package categories."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <category-name> ...")
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -173,3 +201,142 @@ fn delete(names: &Vec<String>, config: &mut RuntimeConfig) -> () {
         }
     }
 }
+
+impl CLICommand for PackageCategorySetDefault {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["set-default"]
+    }
+
+    fn description(&self) -> &str {
+        "Set the default package of the category by its name. Outside of update, it is saved right away."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("<package-name>")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the package belongs to.",
+            },
+        ]
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let _category_name = args.optional_argument(format!("--category-name"))
+            .or(args.optional_argument(format!("--category")));
+        let category_name = match _category_name {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("category name is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: add --category-name=<name>")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+        let package_name = match args.command.get(0).or(args.non_optional.get(0)) {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("package name is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: set-default --category-name=<name> <package-name>")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+
+        // Changes in update are saved by commit, otherwise the config is saved here.
+        match config.value_mut() {
+            Some(value) => {
+                return set_default(&category_name, package_name, value);
+            }
+            None => {}
+        }
+
+        let existed = config.value().clone();
+        let mut updated = existed.clone();
+        set_default(&category_name, package_name, &mut updated)?;
+
+        let mut errors: Vec<CommandError> = vec![];
+        collect_report(updated.validate(), false, &mut errors)?;
+
+        if !errors.is_empty() {
+            errors.insert(
+                0,
+                CommandError::String(
+                    format!("failed validation before saving...")
+                )
+            );
+
+            return Err(errors);
+        }
+
+        updated.package_category_hash = match updated.hash() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't get hash for changed config.")
+                    ),
+                    CommandError::Others(Box::new(error))
+                ]);
+            }
+        };
+
+        let mut plan = RegistrationPlan::default();
+        plan.steps.push(RegistrationStep::SaveConfig {
+            config: Box::new(updated),
+            previous: Box::new(existed),
+        });
+
+        registration_executor(resolver)?.execute(&plan)?;
+
+        println!("Saved changes successfully!");
+
+        Ok(())
+    }
+}
+
+fn set_default(category_name: &String, package_name: &String, config: &mut RuntimeConfig) -> Result<(), Vec<CommandError>> {
+    let category = match config.package_categories.iter_mut().find(| value | value.name.eq(category_name)) {
+        None => {
+            return Err(vec![
+                CommandError::String(
+                    format!("couldn't find package category {}.", category_name)
+                )
+            ]);
+        }
+        Some(value) => value,
+    };
+
+    if !category.packages.iter().any(| value | value.name.eq(package_name)) {
+        let names: Vec<&str> = category.packages.iter()
+            .map(| value | value.name.as_str())
+            .collect();
+
+        return Err(vec![
+            CommandError::String(
+                format!("category {} has no package named {}.", category_name, package_name)
+            ),
+            CommandError::String(
+                format!("Available packages: {}", names.join(", "))
+            )
+        ]);
+    }
+
+    category.default_package = Some(package_name.to_string());
+
+    println!("Set {} as default package of {}.", package_name, category_name);
+
+    Ok(())
+}
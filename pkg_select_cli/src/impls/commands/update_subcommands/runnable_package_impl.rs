@@ -1,10 +1,11 @@
 use std::rc::Rc;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
-use pkg_select_shared::{MutationLocker};
+use pkg_select_shared::{MutationLocker, print_dbg_on_debug};
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use pkg_select_shared::common_models::models::configurations::runnable_package::RunnablePackage;
 use crate::models::commands::update_subcommands::runnable_package::{RunnablePackage as RunnablePackageCommand, RunnablePackageDelete, RunnablePackageUpdate};
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
 use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
@@ -15,6 +16,10 @@ impl CommandCollection for RunnablePackageCommand {
         vec!["package", "runnable-package"]
     }
 
+    fn description(&self) -> &str {
+        "Manage runnable packages in a category."
+    }
+
     fn commands(&self) -> Vec<CommandOrCollection> {
         vec![
             CommandOrCollection::Command(Rc::new(RunnablePackageUpdate {})),
@@ -28,6 +33,29 @@ impl CLICommand for RunnablePackageUpdate {
         vec!["update", "up"]
     }
 
+    fn description(&self) -> &str {
+        "Add runnable packages into the category."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <package-name> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--home"],
+                value: Some("path"),
+                description: "Home of the packages referred as ${PACKAGE_HOME}. Relative target paths resolve against it. Updates existing packages as well.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -40,7 +68,7 @@ impl CLICommand for RunnablePackageUpdate {
             Some(value) => value,
         };
 
-        dbg!(args);
+        print_dbg_on_debug!(args);
 
         let _category_name = args.optional_argument(format!("--category-name"))
             .or(args.optional_argument(format!("--category")));
@@ -58,10 +86,13 @@ impl CLICommand for RunnablePackageUpdate {
             Some(value) => value,
         };
 
+        let home = args.optional_argument(format!("--home"));
+
         let targets: Vec<RunnablePackage> = args.non_optional.iter()
             .map(| value | {
                 let mut instance = RunnablePackage::default();
                 (&mut instance).name = value.to_string();
+                (&mut instance).home = home.clone();
 
                 instance
             })
@@ -120,6 +151,19 @@ fn update(category_name: &String, values: &Vec<RunnablePackage>, config: &mut Ru
     };
 
     for (index, value) in values.iter().enumerate() {
+        let existing = category.packages.iter_mut()
+            .find(| value2 | value2.name.eq(&value.name));
+
+        match (existing, &value.home) {
+            (Some(value2), Some(value3)) => {
+                value2.home = Some(value3.to_string());
+
+                println!("Updated home of runnable package {}.", value.name);
+                continue;
+            }
+            _ => {}
+        }
+
         let duplicated_index = duplicated_indices.iter()
             .position(| value | index.eq(value));
 
@@ -128,6 +172,12 @@ fn update(category_name: &String, values: &Vec<RunnablePackage>, config: &mut Ru
                 (&mut category.packages).push(value.clone());
 
                 println!("Added runnable package {}.", value.name);
+
+                if category.default_package.is_none() {
+                    category.default_package = Some(value.name.to_string());
+
+                    println!("Set {} as default package of {}.", value.name, category.name);
+                }
             }
             Some(_) => {
                 return Err(vec![
@@ -147,6 +197,24 @@ impl CLICommand for RunnablePackageDelete {
         vec!["delete", "del"]
     }
 
+    fn description(&self) -> &str {
+        "Remove runnable packages from the category."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <package-name> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -214,16 +282,13 @@ fn delete(category_name: &String,  names: &Vec<String>, config: &mut RuntimeConf
             Some(value2) => {
                 category.packages.remove(value2);
 
-                match category.default_package {
-                    None => {}
-                    Some(value3) => {
-                        if value2 == value3 {
-                            category.default_package = None;
-                        }
-                    }
-                }
-
                 println!("Removed runnable package {}.", value);
+
+                if category.default_package.as_ref().map(| value3 | value3.eq(value)).unwrap_or(false) {
+                    category.default_package = None;
+
+                    println!("{} was the default package. Run \"category set-default\" to choose another one.", value);
+                }
             }
         }
     }
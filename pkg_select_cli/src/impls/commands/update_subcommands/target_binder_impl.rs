@@ -1,15 +1,19 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::io::{stdin, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use colored::Colorize;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
-use pkg_select_shared::{MutationLocker, safe_canonicalize};
+use pkg_select_shared::{is_executable_file, match_name_pattern, MutationLocker, read_input, safe_canonicalize};
 use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::configurations::runnable_package::RunnablePackage;
 use pkg_select_shared::common_models::models::configurations::target_binder::TargetBinder;
 use pkg_select_shared::common_models::models::errors::canonical_path_combo::IOCanonicalError;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
-use crate::models::commands::update_subcommands::target_binder::{TargetBinder as TargetBinderCommand, TargetBinderDelete, TargetBinderUpdate};
+use crate::models::commands::update_subcommands::target_binder::{TargetBinder as TargetBinderCommand, TargetBinderDelete, TargetBinderScan, TargetBinderUpdate};
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
 use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
@@ -20,10 +24,15 @@ impl CommandCollection for TargetBinderCommand {
         vec!["binder", "target-binder"]
     }
 
+    fn description(&self) -> &str {
+        "Manage executables bound into a package."
+    }
+
     fn commands(&self) -> Vec<CommandOrCollection> {
         vec![
             CommandOrCollection::Command(Rc::new(TargetBinderUpdate {})),
             CommandOrCollection::Command(Rc::new(TargetBinderDelete {})),
+            CommandOrCollection::Command(Rc::new(TargetBinderScan {})),
         ]
     }
 }
@@ -33,6 +42,29 @@ impl CLICommand for TargetBinderUpdate {
         vec!["update", "up"]
     }
 
+    fn description(&self) -> &str {
+        "Bind executables into the package. The runner is placed in execution path with the same filename."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <target-file-path>=<execution-path> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--package-name", "--package"],
+                value: Some("name"),
+                description: "Name of package which the targets belong to.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -129,6 +161,36 @@ fn update(category_name: &String, package_name: &String, values: &HashMap<String
     };
 
     for (target, exec) in values.iter() {
+        // Targets with references or relative to home are kept as they are, so that they follow home of the package.
+        if target.contains("${") || (package.home.is_some() && Path::new(target).is_relative()) {
+            let binder = match raw_binder(package, target, exec) {
+                Ok(value) => value,
+                Err(error) => {
+                    return Err(error);
+                }
+            };
+
+            match package.binders.iter().any(| value | value.convert_exec_to_pathbuf().eq(&binder.convert_exec_to_pathbuf())) {
+                true => {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("{}", binder.convert_exec_to_pathbuf().to_string_lossy())
+                        ),
+                        CommandError::String(
+                            format!("Execution path is duplicated")
+                        )
+                    ]);
+                }
+                false => {
+                    println!("Added target binder {} runnable package {}.", &binder.target_name, package.name);
+
+                    package.binders.push(binder);
+                }
+            }
+
+            continue;
+        }
+
         let converted_target = match safe_canonicalize(&PathBuf::from(target)) {
             Ok(value) => value,
             Err(error) => {
@@ -215,11 +277,90 @@ fn update(category_name: &String, package_name: &String, values: &HashMap<String
     Ok(())
 }
 
+/**
+   Binder with given target as is, after checking it resolves to an existing file like the others.
+**/
+fn raw_binder(package: &RunnablePackage, target: &String, exec: &String) -> Result<TargetBinder, Vec<CommandError>> {
+    let target_name = match Path::new(target).file_name() {
+        None => {
+            return Err(vec![
+                CommandError::String(
+                    format!("{}", target.yellow())
+                ),
+                CommandError::String(
+                    format!("{}", "target filename not selected".bright_red())
+                ),
+                CommandError::String(
+                    format!("Hint: Make sure there is filename on the left path")
+                )
+            ]);
+        }
+        Some(value) => value.to_string_lossy().to_string(),
+    };
+    let target_path = match Path::new(target).parent().map(| value | value.to_string_lossy().to_string()) {
+        Some(value) if !value.is_empty() => value,
+        _ => format!("."),
+    };
+    let exec_path = match safe_canonicalize(&PathBuf::from(exec)) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+    let binder = TargetBinder {
+        target_name,
+        target_path,
+        execution_path: exec_path.to_string_lossy().to_string(),
+    };
+
+    let mut resolving = package.clone();
+    resolving.binders = vec![binder.clone()];
+
+    let (resolved, errors) = resolving.interpolated();
+    if !errors.is_empty() {
+        return Err(errors.iter()
+            .map(| (value, error) | CommandError::String(format!("{}: {}", value, error.error_type)))
+            .collect());
+    }
+
+    match safe_canonicalize(&resolved.binders[0].convert_target_to_pathbuf()) {
+        Ok(_) => Ok(binder),
+        Err(error) => Err(vec![
+            CommandError::Others(Box::new(error))
+        ]),
+    }
+}
+
 impl CLICommand for TargetBinderDelete {
     fn command_names(&self) -> Vec<&str> {
         vec!["delete", "del"]
     }
 
+    fn description(&self) -> &str {
+        "Remove binders from the package by their runner paths."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("-- <execution-path>/<filename> ...")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--package-name", "--package"],
+                value: Some("name"),
+                description: "Name of package which the targets belong to.",
+            },
+        ]
+    }
+
     fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let config_mut = match config.value_mut() {
             None => {
@@ -347,3 +488,325 @@ fn delete(category_name: &String, package_name: &String, names: &Vec<String>, co
 
     Ok(())
 }
+
+impl CLICommand for TargetBinderScan {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["scan"]
+    }
+
+    fn description(&self) -> &str {
+        "Add a binder for every executable in a directory such as bin of a JDK."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--category-name", "--category"],
+                value: Some("name"),
+                description: "Name of category which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--package-name", "--package"],
+                value: Some("name"),
+                description: "Name of package which the targets belong to.",
+            },
+            CommandOption {
+                names: vec!["--dir"],
+                value: Some("path"),
+                description: "Directory to scan. It is kept as is when it has references or is relative to home of the package.",
+            },
+            CommandOption {
+                names: vec!["--exec-path"],
+                value: Some("path"),
+                description: "Execution path of the binders. Defaults to the one the package already uses.",
+            },
+            CommandOption {
+                names: vec!["--include"],
+                value: Some("glob"),
+                description: "Add only executables whose names match, such as \"java*\".",
+            },
+            CommandOption {
+                names: vec!["--exclude"],
+                value: Some("glob"),
+                description: "Skip executables whose names match.",
+            },
+            CommandOption {
+                names: vec!["--yes"],
+                value: None,
+                description: "Add scanned binders without asking. It is implied in scripts and without terminal.",
+            },
+        ]
+    }
+
+    fn main(&self, _: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let config_mut = match config.value_mut() {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't get mutable config")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+
+        let _category_name = args.optional_argument(format!("--category-name"))
+            .or(args.optional_argument(format!("--category")));
+        let category_name = match _category_name {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("category name is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: add --category-name=<name>")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+
+        let _package_name = args.optional_argument(format!("--package-name"))
+            .or(args.optional_argument(format!("--package")));
+        let package_name = match _package_name {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("package name is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: add --package-name=<name>")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+
+        let dir = match args.optional_argument(format!("--dir")) {
+            None => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("directory to scan is undefined.")
+                    ),
+                    CommandError::String(
+                        format!("Hint: add --dir=<path>")
+                    )
+                ])
+            }
+            Some(value) => value,
+        };
+
+        scan(
+            &category_name,
+            &package_name,
+            &dir,
+            &args.optional_argument(format!("--exec-path")),
+            &args.optional_argument(format!("--include")),
+            &args.optional_argument(format!("--exclude")),
+            args.optional_flag(format!("--yes")),
+            config_mut,
+        )
+    }
+}
+
+fn scan(
+    category_name: &String,
+    package_name: &String,
+    dir: &String,
+    exec: &Option<String>,
+    include: &Option<String>,
+    exclude: &Option<String>,
+    confirmed: bool,
+    config: &mut RuntimeConfig,
+) -> Result<(), Vec<CommandError>> {
+    let _category = config.package_categories.iter_mut()
+        .find(| value | value.name.eq(category_name));
+    let category = match _category {
+        None => {
+            return Err(vec![
+                CommandError::String(
+                    format!("category not selected")
+                )
+            ])
+        },
+        Some(value) => value,
+    };
+
+    let _package = category.packages.iter_mut()
+        .find(| value | value.name.eq(package_name));
+    let package = match _package {
+        None => {
+            return Err(vec![
+                CommandError::String(
+                    format!("package not selected")
+                )
+            ])
+        },
+        Some(value) => value,
+    };
+
+    let exec_path = match scan_exec_path(package, exec) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(error);
+        }
+    };
+
+    // Directory is resolved like target paths, so that it can be kept as is and follow home of the package.
+    let keeps_raw = dir.contains("${") || (package.home.is_some() && Path::new(dir).is_relative());
+    let mut resolving = package.clone();
+    resolving.binders = vec![TargetBinder {
+        target_name: format!(""),
+        target_path: dir.to_string(),
+        execution_path: format!(""),
+    }];
+
+    let (resolved, errors) = resolving.interpolated();
+    if !errors.is_empty() {
+        return Err(errors.iter()
+            .map(| (value, error) | CommandError::String(format!("{}: {}", value, error.error_type)))
+            .collect());
+    }
+
+    let scanned_dir = match safe_canonicalize(&PathBuf::from(&resolved.binders[0].target_path)) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+
+    if scanned_dir.eq(&exec_path) {
+        return Err(vec![
+            CommandError::String(
+                format!("{} is the execution path, so binders would execute themselves.", scanned_dir.to_string_lossy())
+            )
+        ]);
+    }
+
+    let target_path = match keeps_raw {
+        true => dir.to_string(),
+        false => scanned_dir.to_string_lossy().to_string(),
+    };
+    let execution_path = exec_path.to_string_lossy().to_string();
+
+    let entries = match fs::read_dir(&scanned_dir) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![
+                CommandError::Others(Box::new(error))
+            ]);
+        }
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(| value | value.ok())
+        .filter(| value | is_executable_file(&value.path()))
+        .map(| value | value.file_name().to_string_lossy().to_string())
+        .filter(| value | include.as_ref().map_or(true, | value2 | match_name_pattern(value2, value)))
+        .filter(| value | exclude.as_ref().map_or(true, | value2 | !match_name_pattern(value2, value)))
+        .collect();
+    names.sort();
+
+    if names.is_empty() {
+        return Err(vec![
+            CommandError::String(
+                format!("couldn't find executables in {}.", scanned_dir.to_string_lossy())
+            )
+        ]);
+    }
+
+    let (bound, added): (Vec<String>, Vec<String>) = names.into_iter()
+        .partition(| value | {
+            package.binders.iter().any(| value2 | {
+                value2.target_name.eq(value) && value2.execution_path.eq(&execution_path)
+            })
+        });
+
+    println!("Found {} executables in {}:", bound.len() + added.len(), scanned_dir.to_string_lossy());
+    for value in added.iter() {
+        println!("  {} {} -> {}", "+".green(), value, exec_path.join(value).to_string_lossy());
+    }
+    for value in bound.iter() {
+        println!("  {} {} (already bound)", "=".dimmed(), value);
+    }
+
+    if added.is_empty() {
+        println!("Every executable is already bound into runnable package {}.", package.name);
+        return Ok(());
+    }
+
+    // Nobody answers the prompt without terminal, so scanned binders are added as listed above.
+    if !confirmed && stdin().is_terminal() {
+        println!("Would you like to add {} binders into runnable package {}?", added.len(), package.name);
+        print!("> ");
+        let input = match read_input() {
+            Ok(value) => value.to_lowercase().replace("\n", ""),
+            Err(error) => {
+                return Err(vec![
+                    CommandError::String(
+                        format!("couldn't get input from terminal.")
+                    ),
+                    CommandError::Others(Box::new(error))
+                ]);
+            }
+        };
+        if !(((&input).len() == 1 && (&input).contains("y")) || (&input).eq(&format!("yes"))) {
+            println!("Skipped scanned binders.");
+            return Ok(());
+        }
+    }
+
+    for value in added.into_iter() {
+        package.binders.push(TargetBinder {
+            target_name: value,
+            target_path: target_path.to_string(),
+            execution_path: execution_path.to_string(),
+        });
+    }
+
+    println!("Added scanned binders into runnable package {}.", package.name);
+
+    Ok(())
+}
+
+/**
+   Execution path given, or the one every binder of the package already uses so that scans can be run again.
+**/
+fn scan_exec_path(package: &RunnablePackage, exec: &Option<String>) -> Result<PathBuf, Vec<CommandError>> {
+    let raw_exec = match exec {
+        Some(value) => value.to_string(),
+        None => {
+            let mut exec_paths: Vec<&String> = package.binders.iter()
+                .map(| value | &value.execution_path)
+                .collect();
+            exec_paths.sort();
+            exec_paths.dedup();
+
+            match exec_paths.len() {
+                1 => exec_paths[0].to_string(),
+                _ => {
+                    return Err(vec![
+                        CommandError::String(
+                            format!("execution path is undefined.")
+                        ),
+                        CommandError::String(
+                            format!("Hint: add --exec-path=<path>")
+                        )
+                    ]);
+                }
+            }
+        }
+    };
+
+    match safe_canonicalize(&PathBuf::from(raw_exec)) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(vec![
+            CommandError::Others(Box::new(error))
+        ]),
+    }
+}
@@ -0,0 +1,166 @@
+use std::io::{stdout, IsTerminal};
+use pkg_select_shared::argument_parser::models::argument::Argument;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::config_resolver::traits::category_search::CategorySearch;
+use pkg_select_shared::{log_error, log_info, project_filename, MutationLocker};
+use pkg_select_shared::ProjectType::ProjectSelectCLI;
+use crate::models::commands::use_package::UsePackage;
+use crate::models::command_option::CommandOption;
+use crate::models::errors::command::CommandError;
+use crate::traits::cli_command::CLICommand;
+
+impl CLICommand for UsePackage {
+    fn command_names(&self) -> Vec<&str> {
+        vec!["use"]
+    }
+
+    fn description(&self) -> &str {
+        "Print shell commands pinning a package of the category for the current shell session only. Evaluate the output in the shell."
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some("<category> [<package>]")
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--shell"],
+                value: Some("sh|fish|powershell|cmd"),
+                description: "Shell to print commands for. Defaults to powershell on Windows and sh otherwise.",
+            },
+            CommandOption {
+                names: vec!["--unset"],
+                value: None,
+                description: "Print commands removing the pin of the category instead.",
+            },
+        ]
+    }
+
+    fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
+        let unset = args.optional_flag(format!("--unset"));
+        let shell = args.optional_argument(format!("--shell"))
+            .unwrap_or(default_shell());
+        let names: Vec<&String> = args.command.iter()
+            .chain(args.non_optional.iter())
+            .collect();
+
+        // Output is evaluated by the shell, so errors are written to stderr only.
+        let category = match names.get(0) {
+            None => {
+                log_error!("category is undefined.");
+                log_error!("Hint: use <category> <package> [--shell=<shell>]");
+
+                return Err(vec![CommandError::None]);
+            }
+            Some(value) => match resolver.category_resolver.find_by_name(config.value(), value) {
+                None => {
+                    log_error!("couldn't find category named {}.", value);
+
+                    return Err(vec![CommandError::None]);
+                }
+                Some(value2) => value2,
+            },
+        };
+        let key = category.override_env_key();
+
+        let line = if unset {
+            unset_line(&shell, &key)
+        } else {
+            let package_name = match names.get(1) {
+                None => {
+                    log_error!("package is undefined.");
+                    log_error!("Hint: use <category> <package> [--shell=<shell>], or use <category> --unset");
+
+                    return Err(vec![CommandError::None]);
+                }
+                Some(value) => value,
+            };
+
+            if !category.packages.iter().any(| value | value.name.eq(*package_name)) {
+                let names: Vec<&str> = category.packages.iter()
+                    .map(| value | value.name.as_str())
+                    .collect();
+
+                log_error!("category {} has no package named {}.", category.name, package_name);
+                log_error!("Available packages: {}", names.join(", "));
+
+                return Err(vec![CommandError::None]);
+            }
+
+            export_line(&shell, &key, package_name)
+        };
+
+        let line = match line {
+            None => {
+                log_error!("unsupported shell {}. Choose one of sh, fish, powershell or cmd.", shell);
+
+                return Err(vec![CommandError::None]);
+            }
+            Some(value) => value,
+        };
+
+        println!("{}", line);
+
+        if stdout().is_terminal() {
+            log_info!("Evaluate the line above to apply it, for example:");
+            log_info!("  {}", eval_hint(&shell, &args_text(&category.name, names.get(1), unset)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_family = "windows")]
+fn default_shell() -> String {
+    format!("powershell")
+}
+
+#[cfg(not(target_family = "windows"))]
+fn default_shell() -> String {
+    format!("sh")
+}
+
+fn export_line(shell: &str, key: &str, value: &str) -> Option<String> {
+    match shell {
+        "sh" | "bash" | "zsh" => Some(format!("export {}={}", key, quote_posix(value))),
+        "fish" => Some(format!("set -gx {} {}", key, quote_posix(value))),
+        "powershell" | "pwsh" => Some(format!("$env:{} = '{}'", key, value.replace("'", "''"))),
+        "cmd" => Some(format!("set \"{}={}\"", key, value)),
+        _ => None,
+    }
+}
+
+fn unset_line(shell: &str, key: &str) -> Option<String> {
+    match shell {
+        "sh" | "bash" | "zsh" => Some(format!("unset {}", key)),
+        "fish" => Some(format!("set -e {}", key)),
+        "powershell" | "pwsh" => Some(format!("Remove-Item Env:{} -ErrorAction SilentlyContinue", key)),
+        "cmd" => Some(format!("set \"{}=\"", key)),
+        _ => None,
+    }
+}
+
+fn quote_posix(value: &str) -> String {
+    format!("'{}'", value.replace("'", "'\\''"))
+}
+
+fn args_text(category: &String, package: Option<&&String>, unset: bool) -> String {
+    match (package, unset) {
+        (_, true) => format!("{} --unset", category),
+        (Some(value), false) => format!("{} {}", category, value),
+        (None, false) => category.to_string(),
+    }
+}
+
+fn eval_hint(shell: &str, args: &String) -> String {
+    let command = format!("{} use {}", project_filename(ProjectSelectCLI), args);
+
+    match shell {
+        "fish" => format!("{} --shell=fish | source", command),
+        "powershell" | "pwsh" => format!("{} --shell=powershell | Invoke-Expression", command),
+        "cmd" => format!("for /f \"delims=\" %i in ('{} --shell=cmd') do %i", command),
+        _ => format!("eval \"$({})\"", command),
+    }
+}
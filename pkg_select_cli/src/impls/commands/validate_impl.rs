@@ -6,14 +6,18 @@ use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersis
 use pkg_select_shared::config_resolver::traits::package_search::PackageSearch;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::common_models::traits::validator::Validator;
+use pkg_select_shared::common_models::impls::errors::validation_impl::child_location;
+use pkg_select_shared::common_models::models::errors::validation::Severity;
 use pkg_select_shared::{current_exec_file_path, MutationLocker, PathPop, print_dbg_on_debug, project_filename};
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
 use pkg_select_shared::common_models::traits::hashable_result::HashableResult;
 use pkg_select_shared::ProjectType::ProjectSelectRunner;
 use crate::impls::path_registration::{check_path_registered, path_registration_resolver};
+use crate::impls::registration_execution_impl::update_runner_index;
 use crate::models::binder_registration_resolver::BinderRegistrationResolver;
 use crate::models::commands::validate::Validate;
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
 use crate::models::errors::path_binder_registration::PathBinderRegistrationError;
 use crate::models::errors::path_binder_registration::Type::{BinderNotRegistered, PathNotRegistered};
@@ -21,15 +25,43 @@ use crate::models::errors::path_binder_registration_combo::DirectoryIOPathBinder
 use crate::models::path_registration_resolver::PathRegistrationResolver;
 use crate::traits::binder_registration::BinderRegistration;
 use crate::traits::cli_command::CLICommand;
+use crate::utils::validation::{collect_report, strict_option};
 
 impl CLICommand for Validate {
     fn command_names(&self) -> Vec<&str> {
         vec!["validate", "val"]
     }
 
+    fn description(&self) -> &str {
+        "Validate config and check if runners and paths of every binder are registered."
+    }
+
+    fn options(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption {
+                names: vec!["--target"],
+                value: Some("name"),
+                description: "Validate the given category or package only.",
+            },
+            CommandOption {
+                names: vec!["--skip-registration"],
+                value: None,
+                description: "Skip checking registration of runners and paths.",
+            },
+            CommandOption {
+                names: vec!["--skip-save"],
+                value: None,
+                description: "Skip saving the refreshed hash of config.",
+            },
+            strict_option(),
+        ]
+    }
+
     fn main(&self, resolver: &ConfigResolver, config: &mut MutationLocker<RuntimeConfig>, args: &Argument) -> Result<(), Vec<CommandError>> {
         let target_arg = args.optional_argument(format!("--target"));
         let skip_registration = args.optional_flag(format!("--skip-registration"));
+        let skip_save = args.optional_flag(format!("--skip-save"));
+        let strict = args.optional_flag(format!("--strict"));
 
         print_dbg_on_debug!(&target_arg);
 
@@ -42,7 +74,7 @@ impl CLICommand for Validate {
         let process_path_without_filename = process_file_path.pop_path();
         let project_filename = project_filename(ProjectSelectRunner);
 
-        let mut copied = config.value_mut().unwrap();
+        let mut copied = config.value().clone();
 
         let binder_registration_resolver = BinderRegistrationResolver {};
         let path_registration_resolver = match path_registration_resolver() {
@@ -52,116 +84,86 @@ impl CLICommand for Validate {
             }
         };
 
+        let mut errors: Vec<CommandError> = vec![];
+        let mut packages: Vec<(String, RunnablePackage)> = vec![];
+
         match &target_arg {
             None => {
-                match copied.validate() {
-                    Ok(_) => {}
-                    Err(error) => {
-                        return Err(vec![CommandError::Others(Box::new(error))]);
-                    }
-                };
-
-                if !skip_registration {
-                    for value in copied.package_categories.iter() {
-                        for value2 in value.packages.iter() {
-                            match validate_binders_in_package(
-                                &process_path_without_filename,
-                                &project_filename,
-                                &binder_registration_resolver,
-                                &path_registration_resolver,
-                                value2,
-                            ) {
-                                Ok(_) => {}
-                                Err(error) => {
-                                    return Err(vec![CommandError::Others(Box::new(error))]);
-                                }
-                            }
-                        }
+                collect_report(copied.validate(), strict, &mut errors)?;
+
+                for value in copied.package_categories.iter() {
+                    for value2 in value.packages.iter() {
+                        packages.push((
+                            child_location(&value.location(), &value2.location()),
+                            value2.clone(),
+                        ));
                     }
                 }
             },
             Some(value) => {
-                let mut ever_found = false;
-
-                let found_category = resolver.category_resolver.find_by_name(copied, value);
-                match found_category {
-                    None => {}
-                    Some(value) => {
-                        ever_found = true;
-
-                        match value.clone().validate() {
-                            Ok(_) => {}
-                            Err(error) => {
-                                return Err(vec![CommandError::Others(Box::new(error))]);
-                            }
-                        }
+                match resolver.category_resolver.find_by_name(&copied, value) {
+                    Some(value2) => {
+                        collect_report(value2.clone().validate(), strict, &mut errors)?;
 
-                        if !skip_registration {
-                            for value in value.packages.iter() {
-                                match validate_binders_in_package(
-                                    &process_path_without_filename,
-                                    &project_filename,
-                                    &binder_registration_resolver,
-                                    &path_registration_resolver,
-                                    value,
-                                ) {
-                                    Ok(_) => {}
-                                    Err(error) => {
-                                        return Err(vec![CommandError::Others(Box::new(error))]);
-                                    }
-                                }
-                            }
+                        for value3 in value2.packages.iter() {
+                            packages.push((
+                                child_location(&value2.location(), &value3.location()),
+                                value3.clone(),
+                            ));
                         }
                     }
-                }
-
-                if !ever_found {
-                    let found_package = resolver.package_resolver.find_by_name(copied, value);
-                    match found_package {
-                        None => {}
-                        Some(value) => {
-                            ever_found = true;
-
-                            match value.clone().validate() {
-                                Ok(_) => {},
-                                Err(error) => {
-                                    return Err(vec![CommandError::Others(Box::new(error))]);
-                                }
+                    None => {
+                        match resolver.package_resolver.find_by_name(&copied, value) {
+                            Some(value2) => {
+                                collect_report(value2.clone().validate(), strict, &mut errors)?;
+                                packages.push((value2.location(), value2.clone()));
                             }
-
-                            if !skip_registration {
-                                match validate_binders_in_package(
-                                    &process_path_without_filename,
-                                    &project_filename,
-                                    &binder_registration_resolver,
-                                    &path_registration_resolver,
-                                    value,
-                                ) {
-                                    Ok(_) => {}
-                                    Err(error) => {
-                                        return Err(vec![CommandError::Others(Box::new(error))]);
-                                    }
-                                }
+                            None => {
+                                return Err(vec![
+                                    CommandError::String(
+                                        format!("not found")
+                                    )
+                                ]);
                             }
                         }
                     }
-
-                    if !ever_found {
-                        return Err(vec![
-                            CommandError::String(
-                                format!("not found")
-                            )
-                        ]);
-                    }
                 }
             }
         };
 
+        if !skip_registration {
+            for (value, value2) in packages.iter() {
+                let mut registration_errors = validate_binders_in_package(
+                    &process_path_without_filename,
+                    &project_filename,
+                    &binder_registration_resolver,
+                    &path_registration_resolver,
+                    value,
+                    value2,
+                );
+
+                errors.append(&mut registration_errors);
+            }
+        }
+
+        if !errors.is_empty() {
+            let count = errors.len();
+            errors.push(
+                CommandError::String(
+                    format!("found {} problems.", count)
+                )
+            );
+
+            return Err(errors);
+        }
+
         match &target_arg {
-            None => {
+            None if !skip_save => {
                 match copied.hash() {
                     Ok(value) => {
-                        if !copied.package_category_hash.eq(&value) {
+                        if copied.package_category_hash.eq(&value) {
+                            update_runner_index(resolver, &copied);
+                        } else {
                             copied.package_category_hash = value;
 
                             match resolver.update(&copied) {
@@ -187,7 +189,7 @@ impl CLICommand for Validate {
                     }
                 }
             }
-            Some(_) => {}
+            _ => {}
         }
 
         println!("Validated successfully!");
@@ -201,9 +203,13 @@ fn validate_binders_in_package(
     source_name: &String,
     binder_resolver: &BinderRegistrationResolver,
     path_resolver: &PathRegistrationResolver,
+    location: &String,
     package: &RunnablePackage,
-) -> Result<(), DirectoryIOPathBinderRegistrationError> {
-    for value in package.binders.iter() {
+) -> Vec<CommandError> {
+    let mut errors: Vec<CommandError> = vec![];
+
+    for (index, value) in package.binders.iter().enumerate() {
+        let binder_location = child_location(location, &format!("binders[{}]", index));
         let binder_registered = match binder_resolver.registered(
             &value.convert_exec_to_pathbuf(),
             process_path_without_filename,
@@ -211,16 +217,20 @@ fn validate_binders_in_package(
         ) {
             Ok(value) => value,
             Err(error) => {
-                return Err(DirectoryIOPathBinderRegistrationError::from(error));
+                errors.push(registration_error(&binder_location, DirectoryIOPathBinderRegistrationError::from(error)));
+                continue;
             }
         };
 
         if !binder_registered {
-            return Err(
-                DirectoryIOPathBinderRegistrationError::PathBinderRegistrationError(
-                    PathBinderRegistrationError {
-                        error_type: BinderNotRegistered(value.target_name.to_string())
-                    }
+            errors.push(
+                registration_error(
+                    &binder_location,
+                    DirectoryIOPathBinderRegistrationError::PathBinderRegistrationError(
+                        PathBinderRegistrationError {
+                            error_type: BinderNotRegistered(value.target_name.to_string())
+                        }
+                    )
                 )
             );
         }
@@ -231,20 +241,30 @@ fn validate_binders_in_package(
         ) {
             Ok(value) => value,
             Err(error) => {
-                return Err(DirectoryIOPathBinderRegistrationError::from(error));
+                errors.push(registration_error(&binder_location, DirectoryIOPathBinderRegistrationError::from(error)));
+                continue;
             }
         };
 
         if !path_registered {
-            return Err(
-                DirectoryIOPathBinderRegistrationError::PathBinderRegistrationError(
-                    PathBinderRegistrationError {
-                        error_type: PathNotRegistered(value.target_name.to_string())
-                    }
+            errors.push(
+                registration_error(
+                    &binder_location,
+                    DirectoryIOPathBinderRegistrationError::PathBinderRegistrationError(
+                        PathBinderRegistrationError {
+                            error_type: PathNotRegistered(value.target_name.to_string())
+                        }
+                    )
                 )
             );
         }
     }
 
-    Ok(())
+    errors
+}
+
+fn registration_error(location: &String, error: DirectoryIOPathBinderRegistrationError) -> CommandError {
+    CommandError::String(
+        format!("{}: {}: {}", Severity::Error, location, error)
+    )
 }
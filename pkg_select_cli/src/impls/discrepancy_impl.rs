@@ -0,0 +1,37 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::models::discrepancy::Discrepancy;
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::MissingRunner(value) => {
+                write!(f, "runner {} doesn't exist.", value.to_string_lossy())
+            }
+            Discrepancy::OutdatedRunner(value) => {
+                write!(f, "runner {} points to the runner in another location.", value.to_string_lossy())
+            }
+            Discrepancy::ForeignFile(value) => {
+                write!(f, "{} isn't runner, but it's execution path of binder.", value.to_string_lossy())
+            }
+            Discrepancy::MissingPath(value) => {
+                write!(f, "path of {} isn't registered.", value.to_string_lossy())
+            }
+            Discrepancy::MissingTarget { category, package, target_path, .. } => {
+                write!(
+                    f,
+                    "target {} of package {} in {} doesn't exist.",
+                    target_path.to_string_lossy(),
+                    package,
+                    category,
+                )
+            }
+            Discrepancy::OrphanRunner(value) => {
+                write!(f, "runner {} isn't bound by any binder in config.", value.to_string_lossy())
+            }
+            Discrepancy::OrphanPath(value) => {
+                write!(f, "path {} is registered, but isn't used by any binder in config.", value.to_string_lossy())
+            }
+        }
+    }
+}
@@ -3,3 +3,5 @@ pub mod command_resolver_impl;
 pub mod errors;
 pub mod binder_registration;
 pub mod path_registration;
+pub mod registration_execution_impl;
+pub mod discrepancy_impl;
@@ -9,7 +9,7 @@ use linux_alternative_resolver_shared::common_models::models::alt_config::AltCon
 use linux_alternative_resolver_shared::common_models::models::link_group::LinkGroup;
 use linux_alternative_resolver_shared::common_models::models::link_item::LinkItem;
 use linux_alternative_resolver_shared::common_models::models::link_path::LinkPath;
-use pkg_select_shared::{InsertTo, Upsert};
+use pkg_select_shared::{InsertTo, print_dbg_on_debug, Upsert};
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
 use crate::models::errors::path_registration::PathRegistrationError;
@@ -17,6 +17,7 @@ use crate::models::errors::path_registration::Type::{DestinationNotFile, Duplica
 use crate::models::errors::path_registration_combo::DirectoryIOPathRegistrationError;
 use crate::models::path_registration_resolver::PathRegistrationResolver;
 use crate::traits::linux_path_registration::{LinuxPathRegistration, LinuxPathRegistrationReset, MultipleLinuxPathRegistration};
+use crate::traits::path_registration_description::{PathRegistrationDescription, PathRegistrationInventory};
 
 const LINUX_BIN_PATH: &str = "/usr/bin";
 
@@ -71,6 +72,75 @@ impl LinuxPathRegistrationReset for PathRegistrationResolver {
     }
 }
 
+impl PathRegistrationDescription for PathRegistrationResolver {
+    fn describe_register(&self, target: &PathBuf) -> Vec<String> {
+        let target_filename = match target.file_name() {
+            None => return vec![],
+            Some(value) => value.to_string_lossy().to_string(),
+        };
+        let bin_path = format!("{}/{}", LINUX_BIN_PATH, &target_filename);
+        let group_exists = self.alt_config.alternatives.iter()
+            .any(| value | value.name.eq(&target_filename));
+        let mut changes: Vec<String> = vec![];
+
+        if !group_exists {
+            changes.push(format!("create alternatives group {}", &target_filename));
+        }
+
+        changes.push(
+            format!("add alternatives item {} to group {} with the highest priority", target.to_string_lossy(), &target_filename)
+        );
+        changes.push(format!("link {} to {}", &bin_path, target.to_string_lossy()));
+
+        changes
+    }
+
+    fn describe_unregister(&self, target: &PathBuf) -> Vec<String> {
+        let target_filename = match target.file_name() {
+            None => return vec![],
+            Some(value) => value.to_string_lossy().to_string(),
+        };
+        let bin_path = format!("{}/{}", LINUX_BIN_PATH, &target_filename);
+        let group_removed = self.alt_config.alternatives.iter()
+            .find(| value | value.name.eq(&target_filename))
+            .map(| value | value.items.len() <= 1)
+            .unwrap_or(false);
+        let mut changes = vec![
+            format!("remove alternatives item {} from group {}", target.to_string_lossy(), &target_filename),
+            format!("unlink {} from {}", &bin_path, target.to_string_lossy()),
+        ];
+
+        if group_removed {
+            changes.push(format!("remove alternatives group {}", &target_filename));
+        }
+
+        changes
+    }
+
+    fn describe_reset(&self) -> Vec<String> {
+        vec![format!("remove every alternatives item pointing to execution paths in config")]
+    }
+}
+
+impl PathRegistrationInventory for PathRegistrationResolver {
+    fn registered_paths(&self) -> Result<Vec<PathBuf>, DirectoryIOPathRegistrationError> {
+        let mut paths: Vec<PathBuf> = vec![];
+
+        for value in self.alt_config.alternatives.iter() {
+            for value2 in value.items.iter() {
+                match value2.paths.get_index(0) {
+                    None => {}
+                    Some(value3) => {
+                        paths.push(PathBuf::from(&value3.alternative_path));
+                    }
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+}
+
 fn reset_registrations(
     resolver: &mut PathRegistrationResolver,
     runtime_config: &RuntimeConfig,
@@ -255,7 +325,7 @@ fn register(
         }
     }
 
-    dbg!(&link_path.name);
+    print_dbg_on_debug!(&link_path.name);
 
     match (&link_path).register() {
         Ok(_) => {},
@@ -8,6 +8,7 @@ use crate::models::errors::path_registration::Type::{DuplicatedTarget, Duplicate
 use crate::models::errors::path_registration_combo::DirectoryIOPathRegistrationError;
 use crate::models::path_registration_resolver::PathRegistrationResolver;
 use crate::traits::path_registration::{MultiplePathRegistration, PathRegistration, PathRegistrationReset};
+use crate::traits::path_registration_description::{PathRegistrationDescription, PathRegistrationInventory};
 
 impl PathRegistration for PathRegistrationResolver {
     fn registered(&self, target: &PathBuf) -> Result<bool, DirectoryIOPathRegistrationError> {
@@ -114,6 +115,69 @@ impl PathRegistrationReset for PathRegistrationResolver {
     }
 }
 
+impl PathRegistrationDescription for PathRegistrationResolver {
+    fn describe_register(&self, target: &PathBuf) -> Vec<String> {
+        let env_path = describe_env_path();
+
+        vec![
+            format!("append \"{}\" to {}", format_raw_data(&describe_target(target)), &env_path),
+            format!("append \"source {}\" to {} if missing", &env_path, PROFILES.join(", ")),
+        ]
+    }
+
+    fn describe_unregister(&self, target: &PathBuf) -> Vec<String> {
+        vec![
+            format!("remove \"{}\" from {}", format_raw_data(&describe_target(target)), describe_env_path()),
+        ]
+    }
+
+    fn describe_reset(&self) -> Vec<String> {
+        let env_path = describe_env_path();
+
+        vec![
+            format!("remove \"source {}\" from {}", &env_path, PROFILES.join(", ")),
+            format!("remove {}", &env_path),
+        ]
+    }
+}
+
+impl PathRegistrationInventory for PathRegistrationResolver {
+    fn registered_paths(&self) -> Result<Vec<PathBuf>, DirectoryIOPathRegistrationError> {
+        let file = match fs::read_to_string(user_dir_path()?.join(PACKAGE_SELECT_ENV)) {
+            Ok(value) => value,
+            Err(_) => format!(""),
+        };
+        let prefix = format_raw_data(&format!(""));
+        let prefix = prefix.trim_end_matches("\"");
+
+        Ok(
+            file.lines()
+                .filter(| value | value.starts_with(prefix))
+                .map(| value | value[prefix.len()..].trim_end_matches("\""))
+                .filter(| value | !value.is_empty())
+                .map(| value | PathBuf::from(value))
+                .collect()
+        )
+    }
+}
+
+fn describe_target(target: &PathBuf) -> String {
+    let mut path = PathBuf::new().join(target);
+
+    if path.is_file() {
+        let _ = &path.pop();
+    }
+
+    path.to_string_lossy().to_string()
+}
+
+fn describe_env_path() -> String {
+    match user_dir_path() {
+        Ok(value) => value.join(PACKAGE_SELECT_ENV).to_string_lossy().to_string(),
+        Err(_) => format!("~/{}", PACKAGE_SELECT_ENV),
+    }
+}
+
 const PACKAGE_SELECT_ENV: &str = ".package-select-env";
 const PROFILES: [&str; 4] = [
     ".zshenv", // usually for decent macOS
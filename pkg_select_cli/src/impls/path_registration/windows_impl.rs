@@ -12,6 +12,7 @@ use crate::models::errors::path_registration_combo::DirectoryIOPathRegistrationE
 use crate::models::errors::windows_error_wrapper::{ErrorCodes, WindowsError};
 use crate::models::path_registration_resolver::PathRegistrationResolver;
 use crate::traits::path_registration::{MultiplePathRegistration, PathRegistration, PathRegistrationReset};
+use crate::traits::path_registration_description::{PathRegistrationDescription, PathRegistrationInventory};
 
 impl PathRegistration for PathRegistrationResolver {
     fn registered(&self, target: &PathBuf) -> Result<bool, DirectoryIOPathRegistrationError> {
@@ -165,6 +166,54 @@ impl PathRegistrationReset for PathRegistrationResolver {
     }
 }
 
+impl PathRegistrationDescription for PathRegistrationResolver {
+    fn describe_register(&self, target: &PathBuf) -> Vec<String> {
+        vec![
+            format!("append {} to registry value HKCU\\Environment\\{}", describe_target(target), WINDOWS_PACKAGE_SELECT_PATH),
+            format!("append %{}% to registry value HKCU\\Environment\\{} if missing", WINDOWS_PACKAGE_SELECT_PATH, WINDOWS_PATH),
+        ]
+    }
+
+    fn describe_unregister(&self, target: &PathBuf) -> Vec<String> {
+        vec![
+            format!("remove {} from registry value HKCU\\Environment\\{}", describe_target(target), WINDOWS_PACKAGE_SELECT_PATH),
+        ]
+    }
+
+    fn describe_reset(&self) -> Vec<String> {
+        vec![
+            format!("remove %{}% from registry value HKCU\\Environment\\{}", WINDOWS_PACKAGE_SELECT_PATH, WINDOWS_PATH),
+            format!("remove registry value HKCU\\Environment\\{}", WINDOWS_PACKAGE_SELECT_PATH),
+        ]
+    }
+}
+
+impl PathRegistrationInventory for PathRegistrationResolver {
+    fn registered_paths(&self) -> Result<Vec<PathBuf>, DirectoryIOPathRegistrationError> {
+        let mut registry_key = registry_key()?;
+        let (data, _) = get_or_create_registry_set(registry_key, WINDOWS_PACKAGE_SELECT_PATH)?;
+
+        invalidate_registry_key(&mut registry_key)?;
+
+        Ok(
+            data.split(";")
+                .filter(| value | !value.is_empty())
+                .map(| value | PathBuf::from(value))
+                .collect()
+        )
+    }
+}
+
+fn describe_target(target: &PathBuf) -> String {
+    let mut path = PathBuf::new().join(target);
+
+    if path.is_file() {
+        let _ = &path.pop();
+    }
+
+    path.to_string_lossy().to_string()
+}
+
 const WINDOWS_PACKAGE_SELECT_PATH: &str = "Package_Select_Path";
 const WINDOWS_PATH: &str = "Path";
 const MAX_VALUE_LENGTH: u32 = 16383;
@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs::{rename, symlink_metadata};
+use std::io;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
+use pkg_select_shared::config_resolver::traits::runner_index_persistence::RunnerIndexPersistence;
+use pkg_select_shared::{current_exec_file_path, log_warn, PathPop, print_dbg_on_debug, project_filename};
+use pkg_select_shared::ProjectType::ProjectSelectRunner;
+use crate::impls::path_registration::{check_raw_path_registered, path_registration_resolver, register_raw_paths, reset_paths, unregister_raw_paths};
+use crate::models::binder_registration_resolver::BinderRegistrationResolver;
+use crate::models::errors::binder_registration_combo::IOBinderRegistrationError;
+use crate::models::errors::command::CommandError;
+use crate::models::planned_step::PlannedStep;
+use crate::models::registration_executor::RegistrationExecutor;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
+use crate::traits::binder_registration::{BinderRegistration, BinderRegistrationDescription};
+use crate::traits::path_registration_description::PathRegistrationDescription;
+use crate::traits::registration_execution::RegistrationExecution;
+
+/**
+   Runner falls back to the full config when the index is missing or stale, so failing to write it is not fatal.
+   Config saved by [ConfigPersistence::update] has its index written already, so it is needed only when config is left unchanged.
+**/
+pub fn update_runner_index(config_resolver: &ConfigResolver, config: &RuntimeConfig) {
+    match config_resolver.update_index(config) {
+        Ok(_) => {}
+        Err(error) => log_warn!("couldn't update runner index: {}", error),
+    }
+}
+
+pub fn registration_executor(config_resolver: &ConfigResolver) -> Result<RegistrationExecutor, Vec<CommandError>> {
+    let process_file_path = match current_exec_file_path() {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![CommandError::Others(Box::new(error))]);
+        }
+    };
+    let path_registration_resolver = match path_registration_resolver() {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(vec![CommandError::Others(Box::new(error))]);
+        }
+    };
+
+    Ok(RegistrationExecutor {
+        config_resolver,
+        binder_registration_resolver: BinderRegistrationResolver {},
+        path_registration_resolver,
+        process_path_without_filename: process_file_path.pop_path(),
+        project_filename: project_filename(ProjectSelectRunner),
+        replaced_files: HashMap::new(),
+    })
+}
+
+impl fmt::Display for RegistrationStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistrationStep::RegisterBinder(value) => {
+                write!(f, "register runner {}", value.to_string_lossy())
+            }
+            RegistrationStep::UnregisterBinder(value) => {
+                write!(f, "unregister runner {}", value.to_string_lossy())
+            }
+            RegistrationStep::RegisterPath(value) => {
+                write!(f, "register path {}", value.to_string_lossy())
+            }
+            RegistrationStep::UnregisterPath(value) => {
+                write!(f, "unregister path {}", value.to_string_lossy())
+            }
+            RegistrationStep::SaveConfig { .. } => {
+                write!(f, "save config")
+            }
+            RegistrationStep::ResetConfig { .. } => {
+                write!(f, "reset config")
+            }
+            RegistrationStep::ResetPaths { .. } => {
+                write!(f, "reset path registrations")
+            }
+        }
+    }
+}
+
+/**
+   Packages in the same category usually share binders, so same exec paths must be planned only once.
+**/
+fn unique_paths(exec_paths: &Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = vec![];
+
+    for value in exec_paths.iter() {
+        if !paths.contains(value) {
+            paths.push(value.clone());
+        }
+    }
+
+    paths
+}
+
+/**
+   Path which a file at exec path is moved to before runner is registered there.
+**/
+pub(crate) fn backup_path(exec_path: &PathBuf) -> PathBuf {
+    let file_name = exec_path.file_name()
+        .map(| value | value.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    exec_path.with_file_name(format!(".{}.pkg-select-backup", file_name))
+}
+
+/**
+   Moves a file which is not a runner out of exec path, so registering runner doesn't lose it.
+   Returns path of the moved file, or None if nothing is at exec path.
+**/
+pub(crate) fn set_aside_file(exec_path: &PathBuf) -> io::Result<Option<PathBuf>> {
+    match symlink_metadata(exec_path) {
+        Ok(_) => {}
+        Err(error) => {
+            if error.kind() == ErrorKind::NotFound {
+                return Ok(None);
+            }
+
+            return Err(error);
+        }
+    }
+
+    let backup = backup_path(exec_path);
+
+    println!("{} already exists, but it's not proper runner file. Moving it to {}...", exec_path.to_string_lossy(), backup.to_string_lossy());
+    rename(exec_path, &backup)?;
+
+    Ok(Some(backup))
+}
+
+pub(crate) fn restore_file(exec_path: &PathBuf, backup: &PathBuf) -> io::Result<()> {
+    rename(backup, exec_path)
+}
+
+/**
+   Returns the step which undoes given step, except restoring files set aside by registering runners.
+**/
+pub(crate) fn reverted_step(step: &RegistrationStep) -> Result<RegistrationStep, CommandError> {
+    let reverted = match step {
+        RegistrationStep::RegisterBinder(value) => RegistrationStep::UnregisterBinder(value.clone()),
+        RegistrationStep::UnregisterBinder(value) => RegistrationStep::RegisterBinder(value.clone()),
+        RegistrationStep::RegisterPath(value) => RegistrationStep::UnregisterPath(value.clone()),
+        RegistrationStep::UnregisterPath(value) => RegistrationStep::RegisterPath(value.clone()),
+        RegistrationStep::SaveConfig { config, previous } => RegistrationStep::SaveConfig {
+            config: previous.clone(),
+            previous: config.clone(),
+        },
+        RegistrationStep::ResetConfig { previous } => RegistrationStep::SaveConfig {
+            config: previous.clone(),
+            previous: previous.clone(),
+        },
+        RegistrationStep::ResetPaths { .. } => {
+            return Err(
+                CommandError::String(
+                    format!("reset of path registrations can't be reverted.")
+                )
+            );
+        }
+    };
+
+    Ok(reverted)
+}
+
+/**
+   Files set aside may be actual executables of users, so they are kept and reported instead of being removed.
+**/
+fn keep_replaced_files(replaced_files: &mut HashMap<PathBuf, PathBuf>) {
+    let mut kept_files: Vec<(&PathBuf, &PathBuf)> = replaced_files.iter().collect();
+    kept_files.sort();
+
+    for (value, value2) in kept_files.into_iter() {
+        log_warn!(
+            "{} was replaced by runner, and it is kept as {}. Remove it if it isn't needed.",
+            value.to_string_lossy(),
+            value2.to_string_lossy(),
+        );
+    }
+
+    replaced_files.clear();
+}
+
+impl RegistrationExecution for RegistrationExecutor<'_> {
+    fn plan_register(&self, exec_paths: &Vec<PathBuf>) -> Result<Vec<RegistrationStep>, Vec<CommandError>> {
+        let mut binder_steps: Vec<RegistrationStep> = vec![];
+        let mut path_steps: Vec<RegistrationStep> = vec![];
+
+        for value in unique_paths(exec_paths).iter() {
+            if !binder_registered(self, value)? {
+                binder_steps.push(RegistrationStep::RegisterBinder(value.clone()));
+            }
+
+            if !path_registered(self, value)? {
+                path_steps.push(RegistrationStep::RegisterPath(value.clone()));
+            }
+        }
+
+        binder_steps.append(&mut path_steps);
+
+        Ok(binder_steps)
+    }
+
+    fn plan_unregister(&self, exec_paths: &Vec<PathBuf>) -> Result<Vec<RegistrationStep>, Vec<CommandError>> {
+        let mut path_steps: Vec<RegistrationStep> = vec![];
+        let mut binder_steps: Vec<RegistrationStep> = vec![];
+
+        for value in unique_paths(exec_paths).iter() {
+            if path_registered(self, value)? {
+                path_steps.push(RegistrationStep::UnregisterPath(value.clone()));
+            }
+
+            if binder_registered(self, value)? {
+                binder_steps.push(RegistrationStep::UnregisterBinder(value.clone()));
+            }
+        }
+
+        path_steps.append(&mut binder_steps);
+
+        Ok(path_steps)
+    }
+
+    fn describe_step(&self, step: &RegistrationStep) -> PlannedStep {
+        let config_path = self.config_resolver.config_path()
+            .map(| value | value.to_string_lossy().to_string())
+            .unwrap_or(format!("config"));
+
+        let (action, target, changes) = match step {
+            RegistrationStep::RegisterBinder(value) => (
+                "register_binder",
+                Some(value),
+                self.binder_registration_resolver.describe_register(
+                    value,
+                    &self.process_path_without_filename,
+                    &self.project_filename,
+                ),
+            ),
+            RegistrationStep::UnregisterBinder(value) => (
+                "unregister_binder",
+                Some(value),
+                self.binder_registration_resolver.describe_unregister(value),
+            ),
+            RegistrationStep::RegisterPath(value) => (
+                "register_path",
+                Some(value),
+                self.path_registration_resolver.describe_register(value),
+            ),
+            RegistrationStep::UnregisterPath(value) => (
+                "unregister_path",
+                Some(value),
+                self.path_registration_resolver.describe_unregister(value),
+            ),
+            RegistrationStep::SaveConfig { .. } => (
+                "save_config",
+                None,
+                vec![
+                    format!("keep current {} in history", &config_path),
+                    format!("write {}", &config_path),
+                ],
+            ),
+            RegistrationStep::ResetConfig { .. } => (
+                "reset_config",
+                None,
+                vec![format!("replace {} with empty config", &config_path)],
+            ),
+            RegistrationStep::ResetPaths { .. } => (
+                "reset_paths",
+                None,
+                self.path_registration_resolver.describe_reset(),
+            ),
+        };
+
+        PlannedStep {
+            action: action.to_string(),
+            target: target.map(| value | value.to_string_lossy().to_string()),
+            changes,
+        }
+    }
+
+    fn describe(&self, plan: &RegistrationPlan) -> Vec<PlannedStep> {
+        plan.steps.iter()
+            .map(| value | self.describe_step(value))
+            .collect()
+    }
+
+    fn apply_step(&mut self, step: &RegistrationStep) -> Result<(), CommandError> {
+        let result: Result<(), Box<dyn std::error::Error>> = match step {
+            RegistrationStep::RegisterBinder(value) => {
+                let backup = match set_aside_file(value) {
+                    Ok(value) => value,
+                    Err(error) => return Err(CommandError::Others(Box::new(error))),
+                };
+                let result = self.binder_registration_resolver.register(
+                    value,
+                    &self.process_path_without_filename,
+                    &self.project_filename,
+                );
+
+                match (result, backup) {
+                    (Ok(_), Some(backup)) => {
+                        self.replaced_files.insert(value.clone(), backup);
+                        Ok(())
+                    }
+                    (Ok(_), None) => Ok(()),
+                    (Err(error), Some(backup)) => {
+                        match restore_file(value, &backup) {
+                            Ok(_) => {}
+                            Err(error2) => log_warn!("couldn't restore {} from {}: {}", value.to_string_lossy(), backup.to_string_lossy(), error2),
+                        }
+
+                        Err(error.into())
+                    }
+                    (Err(error), None) => Err(error.into()),
+                }
+            }
+            RegistrationStep::UnregisterBinder(value) => {
+                self.binder_registration_resolver.unregister(value)
+                    .map_err(| error | error.into())
+            }
+            RegistrationStep::RegisterPath(value) => {
+                register_raw_paths(&mut self.path_registration_resolver, &vec![value])
+                    .map_err(| error | error.into())
+            }
+            RegistrationStep::UnregisterPath(value) => {
+                unregister_raw_paths(&mut self.path_registration_resolver, &vec![value])
+                    .map_err(| error | error.into())
+            }
+            RegistrationStep::SaveConfig { config, .. } => {
+                self.config_resolver.update(config)
+                    .map_err(| error | error.into())
+            }
+            RegistrationStep::ResetConfig { .. } => {
+                self.config_resolver.reset()
+                    .map(| _ | ())
+                    .map_err(| error | error.into())
+            }
+            #[cfg(target_os = "linux")]
+            RegistrationStep::ResetPaths { config } => {
+                reset_paths(&mut self.path_registration_resolver, config)
+                    .map_err(| error | error.into())
+            }
+            #[cfg(not(target_os = "linux"))]
+            RegistrationStep::ResetPaths { .. } => {
+                reset_paths(&mut self.path_registration_resolver)
+                    .map_err(| error | error.into())
+            }
+        };
+
+        result.map_err(| error | CommandError::Others(error))
+    }
+
+    fn revert_step(&mut self, step: &RegistrationStep) -> Result<(), CommandError> {
+        self.apply_step(&reverted_step(step)?)?;
+
+        if let RegistrationStep::RegisterBinder(value) = step {
+            if let Some(backup) = self.replaced_files.remove(value) {
+                return restore_file(value, &backup)
+                    .map_err(| error | CommandError::Others(Box::new(error)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute(&mut self, plan: &RegistrationPlan) -> Result<(), Vec<CommandError>> {
+        let mut applied: Vec<&RegistrationStep> = vec![];
+
+        for value in plan.steps.iter() {
+            print_dbg_on_debug!(value.to_string());
+
+            let error = match self.apply_step(value) {
+                Ok(_) => {
+                    applied.push(value);
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            let mut errors = vec![
+                CommandError::String(
+                    format!("failed to {}.", value)
+                ),
+                error,
+            ];
+            let mut revert_failed = false;
+
+            for value2 in applied.iter().rev() {
+                match self.revert_step(value2) {
+                    Ok(_) => {}
+                    Err(error) => {
+                        revert_failed = true;
+                        errors.push(
+                            CommandError::String(
+                                format!("couldn't revert \"{}\".", value2)
+                            )
+                        );
+                        errors.push(error);
+                    }
+                }
+            }
+
+            if revert_failed {
+                errors.push(
+                    CommandError::String(
+                        format!("some of {} applied steps couldn't be reverted. Run sync to repair registrations.", applied.len())
+                    )
+                );
+            } else {
+                errors.push(
+                    CommandError::String(
+                        format!("reverted {} applied steps. Nothing is changed.", applied.len())
+                    )
+                );
+            }
+
+            self.replaced_files.clear();
+
+            return Err(errors);
+        }
+
+        keep_replaced_files(&mut self.replaced_files);
+
+        Ok(())
+    }
+}
+
+pub(crate) fn binder_registered(executor: &RegistrationExecutor, exec_path: &PathBuf) -> Result<bool, Vec<CommandError>> {
+    match executor.binder_registration_resolver.registered(
+        exec_path,
+        &executor.process_path_without_filename,
+        &executor.project_filename,
+    ) {
+        Ok(value) => Ok(value),
+        Err(error) => {
+            if let IOBinderRegistrationError::IOError(value) = &error {
+                if value.kind() == ErrorKind::NotFound {
+                    return Ok(false);
+                }
+            }
+
+            Err(vec![CommandError::Others(Box::new(error))])
+        }
+    }
+}
+
+pub(crate) fn path_registered(executor: &RegistrationExecutor, exec_path: &PathBuf) -> Result<bool, Vec<CommandError>> {
+    match check_raw_path_registered(&executor.path_registration_resolver, exec_path) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(vec![CommandError::Others(Box::new(error))]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process;
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pkg-select-{}-{}", name, process::id()))
+    }
+
+    #[test]
+    fn registration_steps_are_reverted_to_opposite_ones() {
+        let path = PathBuf::from("/usr/local/bin/java");
+
+        match reverted_step(&RegistrationStep::RegisterBinder(path.clone())) {
+            Ok(RegistrationStep::UnregisterBinder(value)) => assert_eq!(value, path),
+            _ => panic!("register binder must be reverted by unregister binder"),
+        }
+        match reverted_step(&RegistrationStep::UnregisterPath(path.clone())) {
+            Ok(RegistrationStep::RegisterPath(value)) => assert_eq!(value, path),
+            _ => panic!("unregister path must be reverted by register path"),
+        }
+        assert!(reverted_step(&RegistrationStep::ResetPaths { config: Box::default() }).is_err());
+    }
+
+    #[test]
+    fn set_aside_file_is_restored() {
+        let path = temp_path("set-aside");
+        fs::write(&path, "foreign").unwrap();
+
+        let backup = set_aside_file(&path).unwrap().unwrap();
+        assert!(!path.exists());
+
+        restore_file(&path, &backup).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "foreign");
+        assert!(!backup.exists());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn set_aside_file_is_kept_after_plan_succeeds() {
+        let path = temp_path("set-aside-kept");
+        fs::write(&path, "foreign").unwrap();
+
+        let backup = set_aside_file(&path).unwrap().unwrap();
+        fs::write(&path, "runner").unwrap();
+
+        let mut replaced_files: HashMap<PathBuf, PathBuf> = [(path.clone(), backup.clone())].into_iter().collect();
+        keep_replaced_files(&mut replaced_files);
+
+        assert!(replaced_files.is_empty());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "foreign");
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&backup).unwrap();
+    }
+
+    #[test]
+    fn nothing_is_set_aside_for_missing_file() {
+        let path = temp_path("set-aside-missing");
+
+        assert!(set_aside_file(&path).unwrap().is_none());
+    }
+}
@@ -7,15 +7,28 @@ use std::env;
 use std::process::ExitCode;
 use std::rc::Rc;
 use colored::Colorize;
-use pkg_select_shared::{current_exec_file_path, fix_color_options_on_windows, MutationLocker, pause_project_for_debug, print_dbg_on_debug, println_on_debug};
+use pkg_select_shared::{current_exec_file_path, fix_color_options_on_windows, log_error, log_info, log_warn, MutationLocker, pause_project_for_debug, print_dbg_on_debug, println_on_debug, project_filename};
+use pkg_select_shared::logger::logger::{init_logger, set_log_level};
+use pkg_select_shared::logger::models::log_level::LogLevel;
+use pkg_select_shared::ProjectType::ProjectSelectCLI;
 use pkg_select_shared::argument_parser::argument_parser::parse_args;
+use pkg_select_shared::common_models::models::runtime_config::{RuntimeConfig, CURRENT_SCHEMA_VERSION};
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
 use crate::models::command_resolver::CommandResolver;
+use crate::models::commands::category::Category;
 use crate::models::commands::desync::Desync;
+use crate::models::commands::discover::Discover;
+use crate::models::commands::evaluate::Evaluate;
+use crate::models::commands::help::Help;
+use crate::models::commands::doctor::Doctor;
+use crate::models::commands::history::History;
 use crate::models::commands::purge::Purge;
+use crate::models::commands::rollback::Rollback;
 use crate::models::commands::sync::Sync;
+use crate::models::commands::undo::Undo;
 use crate::models::commands::update_config::UpdateConfig;
+use crate::models::commands::use_package::UsePackage;
 use crate::models::commands::validate::Validate;
 use crate::models::errors::command_resolve::CommandResolveError;
 use crate::models::trait_combos::command_combo::CommandOrCollection;
@@ -26,6 +39,16 @@ use crate::utils::root::is_root;
 fn main() -> ExitCode {
     fix_color_options_on_windows();
 
+    let _args: Vec<String> = env::args().skip(1).collect();
+    let mut args = parse_args(_args.join(" "));
+
+    init_logger(LogLevel::Info);
+    if args.optional_flag(format!("--verbose")) {
+        set_log_level(LogLevel::Debug);
+    } else if args.optional_flag(format!("--quiet")) {
+        set_log_level(LogLevel::Error);
+    }
+
     if !is_root() {
         const ROOT_TEXT: &str = if cfg!(target_family = "windows") {
             "administrator privilege"
@@ -33,59 +56,81 @@ fn main() -> ExitCode {
             "root privilege"
         };
 
-        println!("{} {}{}", "Seems like Package Select CLI is running without".bright_red(), ROOT_TEXT.red().bold(), ".".bright_red());
-        println!("{} {}{}", "It must run with".bright_red(), ROOT_TEXT.red().bold(), ".".bright_red());
-        println!("{}", "Please be aware it may not work as intended.".yellow());
+        log_warn!("{} {}{}", "Seems like Package Select CLI is running without".bright_red(), ROOT_TEXT.red().bold(), ".".bright_red());
+        log_warn!("{} {}{}", "It must run with".bright_red(), ROOT_TEXT.red().bold(), ".".bright_red());
+        log_warn!("{}", "Please be aware it may not work as intended.".yellow());
     }
 
-    let _args: Vec<String> = env::args().skip(1).collect();
-    let args = parse_args(_args.join(" "));
     print_dbg_on_debug!(&_args);
     print_dbg_on_debug!(&args);
     print_dbg_on_debug!(env::current_exe().ok());
-    print_dbg_on_debug!(current_exec_file_path().unwrap());
-
-    let config_resolver = ConfigResolver::default();
-    let mut config = match config_resolver.resolve() {
-        Ok(value) => value,
-        Err(error) => {
-            println!("{}", error);
-            pause_project_for_debug();
-
-            return ExitCode::FAILURE;
-        }
-    };
-    let mut config_mutation_locker = MutationLocker::create(&mut config, false);
+    print_dbg_on_debug!(current_exec_file_path());
 
     let mut command_resolver = CommandResolver::default();
     match resolve_commands(&mut command_resolver) {
         Ok(_) => {}
         Err(error) => {
-            println!("{}", error);
-            println!("couldn't resolve commands.");
+            log_error!("{}", error);
+            log_error!("couldn't resolve commands.");
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
     };
 
-    let command_name = (&args).command.join("/");
-    let command = match command_resolver.find_by_name(&command_name) {
+    // Bare --help asks for the overview, not for help of help command itself.
+    let is_bare = args.command.is_empty();
+    if is_bare {
+        args.command.push(format!("help"));
+    }
+
+    let (command, command_length) = match command_resolver.find_by_command(&args.command) {
         None => {
-            println!("couldn't find \"{}\" command", &command_name.replace("/", " "));
+            log_error!("couldn't find \"{}\" command", &args.command.join(" "));
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
         Some(value) => value,
     };
+    let command_name = (&args).command[..command_length].join("/");
+    args.command.drain(..command_length);
+
+    if !is_bare && args.optional_flag(format!("--help")) {
+        let command_path = format!("{} {}", project_filename(ProjectSelectCLI), &command_name.replace("/", " "));
+        println!("{}", command.help(&command_path));
+
+        return ExitCode::SUCCESS;
+    }
+
+    let config_resolver = ConfigResolver::default();
+    // Help must be available even when config is broken, and it doesn't read config anyway.
+    let mut config = match command_name.eq("help") {
+        true => RuntimeConfig::default(),
+        false => match config_resolver.resolve() {
+            Ok(value) => value,
+            Err(error) => {
+                log_error!("{}", error);
+                pause_project_for_debug();
+
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    if !command_name.eq("help") {
+        persist_migration(&config_resolver);
+    }
+
+    let mut config_mutation_locker = MutationLocker::create(&mut config, false);
 
     match command.run(&config_resolver, &mut config_mutation_locker, &args) {
         Ok(_) => {
             println_on_debug!("Successfully finished running {}.", &command_name.replace("/", " "))
         }
         Err(_) => {
-            println!("Failed running {}.", &command_name.replace("/", " "));
+            // Output of commands like use is evaluated by the shell, so failures are written to stderr.
+            log_error!("Failed running {}.", &command_name.replace("/", " "));
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
@@ -97,10 +142,49 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
+pub fn commands() -> Vec<CommandOrCollection> {
+    vec![
+        CommandOrCollection::Command(Rc::new(Sync {})),
+        CommandOrCollection::Command(Rc::new(Desync {})),
+        CommandOrCollection::Command(Rc::new(Validate {})),
+        CommandOrCollection::Command(Rc::new(Evaluate {})),
+        CommandOrCollection::Command(Rc::new(Doctor {})),
+        CommandOrCollection::Command(Rc::new(UsePackage {})),
+        CommandOrCollection::Collection(Rc::new(Category {})),
+        CommandOrCollection::Collection(Rc::new(Discover {})),
+        CommandOrCollection::Command(Rc::new(UpdateConfig {})),
+        CommandOrCollection::Command(Rc::new(Purge {})),
+        CommandOrCollection::Command(Rc::new(History {})),
+        CommandOrCollection::Command(Rc::new(Rollback {})),
+        CommandOrCollection::Command(Rc::new(Undo {})),
+        CommandOrCollection::Command(Rc::new(Help {})),
+    ]
+}
+
+/**
+   Runners migrate config of older schema in memory only, so CLI writes it once for all of them.
+**/
+fn persist_migration(config_resolver: &ConfigResolver) {
+    match config_resolver.migrate() {
+        Ok(None) => {}
+        Ok(Some(value)) => {
+            log_info!(
+                "Migrated config to schema version {}. The previous one is kept as {}.",
+                CURRENT_SCHEMA_VERSION,
+                value.to_string_lossy(),
+            );
+        }
+        Err(error) => {
+            log_warn!("{}", error);
+            log_warn!("couldn't save migrated config. It is migrated again on next run.");
+        }
+    }
+}
+
 fn resolve_commands(resolver: &mut CommandResolver) -> Result<(), CommandResolveError> {
-    resolver.resolve(CommandOrCollection::Command(Rc::new(Sync {})))?;
-    resolver.resolve(CommandOrCollection::Command(Rc::new(Desync {})))?;
-    resolver.resolve(CommandOrCollection::Command(Rc::new(Validate {})))?;
-    resolver.resolve(CommandOrCollection::Command(Rc::new(UpdateConfig {})))?;
-    resolver.resolve(CommandOrCollection::Command(Rc::new(Purge {})))
+    for value in commands().into_iter() {
+        resolver.resolve(value)?;
+    }
+
+    Ok(())
 }
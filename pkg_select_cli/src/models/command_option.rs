@@ -0,0 +1,5 @@
+pub struct CommandOption<'t> {
+    pub names: Vec<&'t str>,
+    pub value: Option<&'t str>,
+    pub description: &'t str,
+}
@@ -0,0 +1,12 @@
+use struct_indexer_macro::{Indexed, ToAnyTrait};
+
+pub struct Discover {}
+
+#[derive(Clone, Indexed, ToAnyTrait)]
+pub struct DiscoverJdk {}
+
+impl Default for DiscoverJdk {
+    fn default() -> Self {
+        DiscoverJdk {}
+    }
+}
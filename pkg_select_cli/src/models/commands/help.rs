@@ -0,0 +1,4 @@
+use struct_indexer_macro::{Indexed, ToAnyTrait};
+
+#[derive(Clone, Indexed, ToAnyTrait)]
+pub struct Help {}
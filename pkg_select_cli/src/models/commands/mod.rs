@@ -1,7 +1,15 @@
 pub mod update_subcommands;
 pub mod desync;
+pub mod evaluate;
 pub mod sync;
 pub mod update_config;
 pub mod validate;
 pub mod purge;
 pub mod help;
+pub mod history;
+pub mod rollback;
+pub mod undo;
+pub mod doctor;
+pub mod use_package;
+pub mod category;
+pub mod discover;
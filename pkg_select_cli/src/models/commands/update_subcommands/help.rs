@@ -0,0 +1,10 @@
+use struct_indexer_macro::{Indexed, ToAnyTrait};
+
+#[derive(Clone, Indexed, ToAnyTrait)]
+pub struct Help {}
+
+impl Default for Help {
+    fn default() -> Self {
+        Help {}
+    }
+}
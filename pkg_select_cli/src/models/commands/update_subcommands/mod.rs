@@ -1,5 +1,6 @@
 pub mod commit_changes;
 pub mod env_var;
+pub mod help;
 pub mod package_category;
 pub mod runnable_package;
 pub mod target_binder;
@@ -19,3 +19,12 @@ impl Default for PackageCategoryDelete {
         PackageCategoryDelete {}
     }
 }
+
+#[derive(Clone, Indexed, ToAnyTrait)]
+pub struct PackageCategorySetDefault {}
+
+impl Default for PackageCategorySetDefault {
+    fn default() -> Self {
+        PackageCategorySetDefault {}
+    }
+}
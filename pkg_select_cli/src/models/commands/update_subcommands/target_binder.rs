@@ -19,3 +19,12 @@ impl Default for TargetBinderDelete {
         TargetBinderDelete {}
     }
 }
+
+#[derive(Clone, Indexed, ToAnyTrait)]
+pub struct TargetBinderScan {}
+
+impl Default for TargetBinderScan {
+    fn default() -> Self {
+        TargetBinderScan {}
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+/**
+   Difference between config and registrations on disk found by doctor.
+   Paths are execution paths of binders unless noted.
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Discrepancy {
+    MissingRunner(PathBuf),
+    /**
+       Runner links to the runner in another location.
+    **/
+    OutdatedRunner(PathBuf),
+    /**
+       Execution path is taken by a file that isn't runner.
+    **/
+    ForeignFile(PathBuf),
+    MissingPath(PathBuf),
+    /**
+       Target binary of binder doesn't exist anymore.
+    **/
+    MissingTarget {
+        category: String,
+        package: String,
+        target_path: PathBuf,
+        exec_path: PathBuf,
+    },
+    /**
+       Runner isn't bound by any binder in config.
+    **/
+    OrphanRunner(PathBuf),
+    /**
+       Registered path isn't used by any binder in config.
+    **/
+    OrphanPath(PathBuf),
+}
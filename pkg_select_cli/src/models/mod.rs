@@ -1,6 +1,11 @@
 pub mod commands;
 pub mod errors;
 pub mod trait_combos;
+pub mod command_option;
 pub mod command_resolver;
 pub mod path_registration_resolver;
 pub mod binder_registration_resolver;
+pub mod registration_plan;
+pub mod registration_executor;
+pub mod planned_step;
+pub mod discrepancy;
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/**
+   Human and machine readable form of a registration step
+   printed by --dry-run and --json.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedStep {
+    pub action: String,
+    pub target: Option<String>,
+    pub changes: Vec<String>,
+}
@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
+use crate::models::binder_registration_resolver::BinderRegistrationResolver;
+use crate::models::path_registration_resolver::PathRegistrationResolver;
+
+pub struct RegistrationExecutor<'a> {
+    pub config_resolver: &'a ConfigResolver,
+    pub binder_registration_resolver: BinderRegistrationResolver,
+    pub path_registration_resolver: PathRegistrationResolver,
+    pub process_path_without_filename: PathBuf,
+    pub project_filename: String,
+    /**
+       Files which were at exec paths before registering runners, keyed by exec path.
+       They are restored when plan is reverted, and removed when plan succeeds.
+    **/
+    pub replaced_files: HashMap<PathBuf, PathBuf>,
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+
+/**
+   A single reversible step of registration plan.
+   Paths are exec paths of target binders.
+**/
+#[derive(Debug, Clone)]
+pub enum RegistrationStep {
+    RegisterBinder(PathBuf),
+    UnregisterBinder(PathBuf),
+    RegisterPath(PathBuf),
+    UnregisterPath(PathBuf),
+    SaveConfig {
+        config: Box<RuntimeConfig>,
+        previous: Box<RuntimeConfig>,
+    },
+    ResetConfig {
+        previous: Box<RuntimeConfig>,
+    },
+    /**
+       Not reversible, so it's supposed to be the last step.
+    **/
+    ResetPaths {
+        config: Box<RuntimeConfig>,
+    },
+}
+
+/**
+   Steps applied in order. When a step fails,
+   every step applied before it is reverted in reverse order.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct RegistrationPlan {
+    pub steps: Vec<RegistrationStep>,
+}
@@ -2,7 +2,31 @@ use std::rc::Rc;
 use crate::traits::cli_command::CLICommand;
 use crate::traits::command_collection::CommandCollection;
 
+#[derive(Clone)]
 pub enum CommandOrCollection {
     Command(Rc<dyn CLICommand>),
     Collection(Rc<dyn CommandCollection>),
 }
+
+impl CommandOrCollection {
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            CommandOrCollection::Command(value) => value.command_names(),
+            CommandOrCollection::Collection(value) => value.collection_names(),
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            CommandOrCollection::Command(value) => value.description(),
+            CommandOrCollection::Collection(value) => value.description(),
+        }
+    }
+
+    pub fn help(&self, command_path: &str) -> String {
+        match self {
+            CommandOrCollection::Command(value) => value.help(command_path),
+            CommandOrCollection::Collection(value) => value.help(command_path),
+        }
+    }
+}
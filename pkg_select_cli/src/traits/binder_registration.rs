@@ -10,3 +10,8 @@ pub trait BinderRegistration {
 pub trait BinderRegistrationReset {
     fn reset(&self) -> Result<(), IOBinderRegistrationError>;
 }
+
+pub trait BinderRegistrationDescription {
+    fn describe_register(&self, exec_path: &PathBuf, process_path_without_filename: &PathBuf, source_name: &String) -> Vec<String>;
+    fn describe_unregister(&self, exec_path: &PathBuf) -> Vec<String>;
+}
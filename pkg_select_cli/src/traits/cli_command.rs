@@ -2,11 +2,56 @@ use struct_indexer_core::{Indexed, ToAnyTrait};
 use pkg_select_shared::argument_parser::models::argument::Argument;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
-use pkg_select_shared::MutationLocker;
+use pkg_select_shared::{log_error, MutationLocker};
+use crate::models::command_option::CommandOption;
 use crate::models::errors::command::CommandError;
+use crate::models::trait_combos::command_combo::CommandOrCollection;
+use crate::utils::help::{command_rows, format_rows, option_rows};
 
 pub trait CLICommand : Indexed + ToAnyTrait {
     fn command_names(&self) -> Vec<&str>;
+    fn description(&self) -> &str;
+    /**
+       Describes values after options such as "-- <name> ...".
+    **/
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+    fn options(&self) -> Vec<CommandOption> {
+        vec![]
+    }
+    /**
+       Commands which can be run only while this command is running, such as commands in update prompt.
+    **/
+    fn subcommands(&self) -> Vec<CommandOrCollection> {
+        vec![]
+    }
+    fn help(&self, command_path: &str) -> String {
+        let mut lines: Vec<String> = vec![];
+
+        match self.usage() {
+            None => lines.push(format!("Usage: {} [options]", command_path)),
+            Some(value) => lines.push(format!("Usage: {} [options] {}", command_path, value)),
+        }
+        if self.command_names().len() > 1 {
+            lines.push(format!("Aliases: {}", self.command_names().join(", ")));
+        }
+
+        lines.push(format!(""));
+        lines.push(self.description().to_string());
+        lines.push(format!(""));
+        lines.push(format!("Options:"));
+        lines.append(&mut format_rows(&option_rows(&self.options())));
+
+        let subcommands = self.subcommands();
+        if !subcommands.is_empty() {
+            lines.push(format!(""));
+            lines.push(format!("Commands:"));
+            lines.append(&mut format_rows(&command_rows(&subcommands, "")));
+        }
+
+        lines.join("\n")
+    }
     fn main(
         &self,
         resolver: &ConfigResolver,
@@ -22,14 +67,15 @@ pub trait CLICommand : Indexed + ToAnyTrait {
         match self.main(resolver, config, args) {
             Ok(_) => Ok(()),
             Err(error) => {
+                // Errors never go to stdout, since output of commands like use is evaluated by shells.
                 for value in error.iter() {
                     match value {
                         CommandError::None => {},
                         CommandError::String(value) => {
-                            println!("{}", value);
+                            log_error!("{}", value);
                         },
                         CommandError::Others(value) => {
-                            println!("{}", value);
+                            log_error!("{}", value);
                         }
                     }
                 }
@@ -4,10 +4,28 @@ use crate::models::errors::command_resolve::CommandResolveError;
 use crate::models::errors::command_resolve::Type::{DuplicatedName, EmptyNameList};
 use crate::models::trait_combos::command_combo::CommandOrCollection;
 use crate::traits::cli_command::CLICommand;
+use crate::utils::help::{command_rows, format_rows};
 
 pub trait CommandCollection {
     fn collection_names(&self) -> Vec<&str>;
+    fn description(&self) -> &str;
     fn commands(&self) -> Vec<CommandOrCollection>;
+    fn help(&self, command_path: &str) -> String {
+        let mut lines: Vec<String> = vec![];
+
+        lines.push(format!("Usage: {} <command> [options]", command_path));
+        if self.collection_names().len() > 1 {
+            lines.push(format!("Aliases: {}", self.collection_names().join(", ")));
+        }
+
+        lines.push(format!(""));
+        lines.push(self.description().to_string());
+        lines.push(format!(""));
+        lines.push(format!("Commands:"));
+        lines.append(&mut format_rows(&command_rows(&self.commands(), "")));
+
+        lines.join("\n")
+    }
     fn resolve(&self, prefixes: &Vec<&str>, destination: &mut HashMap<String, Rc<dyn CLICommand>>) -> Result<(), CommandResolveError> {
         for value in self.commands().into_iter() {
             match value {
@@ -3,4 +3,20 @@ use crate::traits::cli_command::CLICommand;
 
 pub trait CommandSearch {
     fn find_by_name(&self, name: &str) -> Option<&Rc<dyn CLICommand>>;
+    /**
+       Find [CLICommand] by the longest leading words of given command.
+       Returns the command with the number of words consumed, so that the rest can be treated as positional values.
+    **/
+    fn find_by_command(&self, command: &Vec<String>) -> Option<(&Rc<dyn CLICommand>, usize)> {
+        for length in (1..command.len() + 1).rev() {
+            match self.find_by_name(&command[..length].join("/")) {
+                None => {}
+                Some(value) => {
+                    return Some((value, length));
+                }
+            }
+        }
+
+        None
+    }
 }
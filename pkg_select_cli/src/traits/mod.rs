@@ -5,3 +5,5 @@ pub mod command_search;
 pub mod binder_registration;
 pub mod path_registration;
 pub mod linux_path_registration;
+pub mod registration_execution;
+pub mod path_registration_description;
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+use crate::models::errors::path_registration_combo::DirectoryIOPathRegistrationError;
+
+/**
+   Describes what path registration would change on this platform
+   without changing anything.
+**/
+pub trait PathRegistrationDescription {
+    fn describe_register(&self, target: &PathBuf) -> Vec<String>;
+    fn describe_unregister(&self, target: &PathBuf) -> Vec<String>;
+    fn describe_reset(&self) -> Vec<String>;
+}
+
+/**
+   Lists every path registered by Package Select.
+   They are execution paths on Linux, and directories of them on other platforms.
+**/
+pub trait PathRegistrationInventory {
+    fn registered_paths(&self) -> Result<Vec<PathBuf>, DirectoryIOPathRegistrationError>;
+}
@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+use crate::models::errors::command::CommandError;
+use crate::models::planned_step::PlannedStep;
+use crate::models::registration_plan::{RegistrationPlan, RegistrationStep};
+
+pub trait RegistrationExecution {
+    /**
+       Plans registering runners first, then paths of given exec paths.
+       Already registered ones are skipped.
+    **/
+    fn plan_register(&self, exec_paths: &Vec<PathBuf>) -> Result<Vec<RegistrationStep>, Vec<CommandError>>;
+    /**
+       Plans unregistering paths first, then runners of given exec paths.
+       Not registered ones are skipped.
+    **/
+    fn plan_unregister(&self, exec_paths: &Vec<PathBuf>) -> Result<Vec<RegistrationStep>, Vec<CommandError>>;
+    fn describe_step(&self, step: &RegistrationStep) -> PlannedStep;
+    fn describe(&self, plan: &RegistrationPlan) -> Vec<PlannedStep>;
+    fn apply_step(&mut self, step: &RegistrationStep) -> Result<(), CommandError>;
+    fn revert_step(&mut self, step: &RegistrationStep) -> Result<(), CommandError>;
+    /**
+       Applies every step of plan. On failure, reverts applied steps
+       and returns the cause with revert results.
+    **/
+    fn execute(&mut self, plan: &RegistrationPlan) -> Result<(), Vec<CommandError>>;
+}
@@ -0,0 +1,92 @@
+use crate::models::command_option::CommandOption;
+use crate::models::trait_combos::command_combo::CommandOrCollection;
+
+pub fn format_rows(rows: &Vec<(String, String)>) -> Vec<String> {
+    let width = rows.iter()
+        .map(| (name, _) | name.len())
+        .max()
+        .unwrap_or(0);
+
+    rows.iter()
+        .map(| (name, description) | format!("  {:width$}    {}", name, description, width = width))
+        .collect()
+}
+
+pub fn option_rows(options: &Vec<CommandOption>) -> Vec<(String, String)> {
+    let mut rows: Vec<(String, String)> = options.iter()
+        .map(| value | {
+            let names = value.names.join(", ");
+            let name = match value.value {
+                None => names,
+                Some(value2) => format!("{}=<{}>", names, value2),
+            };
+
+            (name, value.description.to_string())
+        })
+        .collect();
+
+    rows.push((format!("--help"), format!("Print help of this command.")));
+
+    rows
+}
+
+pub fn command_rows(commands: &Vec<CommandOrCollection>, prefix: &str) -> Vec<(String, String)> {
+    let mut rows: Vec<(String, String)> = vec![];
+
+    for value in commands.iter() {
+        let name = match value.names().get(0) {
+            None => continue,
+            Some(value2) => join_command_path(prefix, value2),
+        };
+
+        match value {
+            CommandOrCollection::Command(_) => {
+                rows.push((name, value.description().to_string()));
+            }
+            CommandOrCollection::Collection(value2) => {
+                rows.append(&mut command_rows(&value2.commands(), &name));
+            }
+        }
+    }
+
+    rows
+}
+
+/**
+   Find [CommandOrCollection] in given command tree by command words such as ["binder", "update"].
+   Both commands and collections can be found, and their subcommands are searched as well.
+**/
+pub fn find_command(commands: Vec<CommandOrCollection>, words: &[String]) -> Option<CommandOrCollection> {
+    let word = words.get(0)?;
+    let found = commands.into_iter()
+        .find(| value | value.names().contains(&word.as_str()))?;
+
+    if words.len() == 1 {
+        return Some(found);
+    }
+
+    match &found {
+        CommandOrCollection::Command(value) => find_command(value.subcommands(), &words[1..]),
+        CommandOrCollection::Collection(value) => find_command(value.commands(), &words[1..]),
+    }
+}
+
+pub fn join_command_path(prefix: &str, name: &str) -> String {
+    match prefix.is_empty() {
+        true => name.to_string(),
+        false => format!("{} {}", prefix, name),
+    }
+}
+
+pub fn overview(commands: &Vec<CommandOrCollection>, prefix: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+
+    lines.push(format!("Usage: {} [options]", join_command_path(prefix, "<command>")));
+    lines.push(format!(""));
+    lines.push(format!("Commands:"));
+    lines.append(&mut format_rows(&command_rows(commands, "")));
+    lines.push(format!(""));
+    lines.push(format!("Run \"{}\" to see help of each command.", join_command_path(prefix, "help <command>")));
+
+    lines.join("\n")
+}
@@ -1 +1,5 @@
+pub mod help;
 pub mod root;
+pub mod time;
+pub mod plan;
+pub mod validation;
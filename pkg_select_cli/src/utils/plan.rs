@@ -0,0 +1,62 @@
+use serde_json::json;
+use crate::models::command_option::CommandOption;
+use crate::models::errors::command::CommandError;
+use crate::models::planned_step::PlannedStep;
+
+pub fn plan_options() -> Vec<CommandOption<'static>> {
+    vec![
+        CommandOption {
+            names: vec!["--dry-run"],
+            value: None,
+            description: "Print planned changes without applying them.",
+        },
+        CommandOption {
+            names: vec!["--json"],
+            value: None,
+            description: "Print planned changes as JSON. Implies --dry-run.",
+        },
+    ]
+}
+
+/**
+   Print planned steps of the command. Nothing is applied here.
+**/
+pub fn print_plan(command_name: &str, steps: &Vec<PlannedStep>, json: bool) -> Result<(), Vec<CommandError>> {
+    if json {
+        let document = json!({
+            "command": command_name,
+            "steps": steps,
+        });
+
+        match serde_json::to_string_pretty(&document) {
+            Ok(value) => {
+                println!("{}", value);
+            }
+            Err(error) => {
+                return Err(vec![CommandError::Others(Box::new(error))]);
+            }
+        }
+
+        return Ok(());
+    }
+
+    if steps.is_empty() {
+        println!("Nothing to change.");
+        return Ok(());
+    }
+
+    println!("Planned changes of {} (dry run, nothing is applied):", command_name);
+
+    for (index, value) in steps.iter().enumerate() {
+        match &value.target {
+            None => println!("  {}. {}", index + 1, value.action),
+            Some(value2) => println!("  {}. {} {}", index + 1, value.action, value2),
+        }
+
+        for value2 in value.changes.iter() {
+            println!("       - {}", value2);
+        }
+    }
+
+    Ok(())
+}
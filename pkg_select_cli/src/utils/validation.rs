@@ -0,0 +1,44 @@
+use pkg_select_shared::common_models::models::errors::validation::{Severity, ValidationReport};
+use pkg_select_shared::common_models::models::errors::validation_combo::IOCanonicalSerdeValidationError;
+use pkg_select_shared::log_warn;
+use crate::models::command_option::CommandOption;
+use crate::models::errors::command::CommandError;
+
+pub fn strict_option() -> CommandOption<'static> {
+    CommandOption {
+        names: vec!["--strict"],
+        value: None,
+        description: "Treat validation warnings as errors.",
+    }
+}
+
+/**
+   Push every error of validation into errors, and log warnings.
+   With strict, warnings are pushed as errors too.
+**/
+pub fn collect_report(
+    result: Result<ValidationReport, IOCanonicalSerdeValidationError>,
+    strict: bool,
+    errors: &mut Vec<CommandError>,
+) -> Result<(), Vec<CommandError>> {
+    let report = match result {
+        Ok(value) => value,
+        Err(IOCanonicalSerdeValidationError::ValidationReport(value)) => value,
+        Err(error) => {
+            return Err(vec![CommandError::Others(Box::new(error))]);
+        }
+    };
+
+    for value in report.findings.into_iter() {
+        match value.severity {
+            Severity::Warning if !strict => {
+                log_warn!("{}", value);
+            }
+            _ => {
+                errors.push(CommandError::Others(Box::new(value)));
+            }
+        }
+    }
+
+    Ok(())
+}
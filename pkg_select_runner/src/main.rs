@@ -1,118 +1,322 @@
-use colored::Colorize;
 use pkg_select_shared::common_models::traits::binder_converter::BinderConverter;
-use pkg_select_shared::common_models::traits::binder_search::BinderSearch;
 use pkg_select_shared::config_resolver::config_resolver::ConfigResolver;
 use pkg_select_shared::config_resolver::traits::config_persistence::ConfigPersistence;
 use pkg_select_shared::config_resolver::traits::package_search::PackageSearch;
 use pkg_select_shared::{
-    current_exec_file_path, current_working_path, fix_color_options_on_windows,
-    pause_project_for_debug, print_dbg_on_debug,
+    current_exec_file_path, current_working_path, fix_color_options_on_windows, log_error, log_warn,
+    pause_project_for_debug, print_dbg_on_debug, safe_canonicalize, RUNNER_DEPTH_ENV_KEY,
+    RUNNER_DEPTH_LIMIT, RUNNER_PROCESS_ENV_KEY, RUNNER_VISITED_ENV_KEY,
 };
-use std::collections::HashMap;
+use pkg_select_shared::common_models::impls::runtime_config_impl::{resolves_to_runner, runner_paths};
+use pkg_select_shared::common_models::models::configurations::runnable_package::RunnablePackage;
+use pkg_select_shared::common_models::models::errors::package_override::Type::BinderNotInPackage;
+use pkg_select_shared::common_models::models::configurations::target_binder::TargetBinder;
+use pkg_select_shared::common_models::models::runtime_config::RuntimeConfig;
+use pkg_select_shared::config_resolver::traits::runner_index_persistence::RunnerIndexPersistence;
+use pkg_select_shared::logger::logger::init_logger;
+use pkg_select_shared::logger::models::log_level::LogLevel;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
 use std::process::{Command, ExitCode};
 
 fn main() -> ExitCode {
     fix_color_options_on_windows();
+    init_logger(LogLevel::Warn);
 
-    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let package_override = package_override(&args);
     print_dbg_on_debug!(args.join(" "));
+    print_dbg_on_debug!(&package_override);
 
-    let config_resolver = ConfigResolver::default();
-    let config = match config_resolver.resolve() {
+    let depth = runner_depth();
+    if depth >= RUNNER_DEPTH_LIMIT {
+        log_error!("runner is nested {} times. Target of binder may point to runner itself.", depth);
+        log_error!("Run validate to find such binders.");
+        pause_project_for_debug();
+
+        return ExitCode::FAILURE;
+    }
+
+    let working_path = match current_working_path() {
         Ok(value) => value,
         Err(error) => {
-            println!("{}", error);
+            log_error!("{}", error);
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
     };
-    let working_path = match current_working_path() {
+    let exec_path = match current_exec_file_path() {
         Ok(value) => value,
         Err(error) => {
-            println!("{}", error);
+            log_error!("{}", error);
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
     };
-    let exec_path = match current_exec_file_path() {
+
+    print_dbg_on_debug!(working_path.to_string_lossy());
+    print_dbg_on_debug!(exec_path.to_string_lossy());
+
+    let mut visited = visited_exec_paths();
+    if visited.contains(&exec_path) {
+        log_error!("runner {} is executed again by its own target, so it would execute itself forever.", exec_path.to_string_lossy());
+        log_error!("Target of binder may point to runner itself. Run validate to find such binders.");
+        pause_project_for_debug();
+
+        return ExitCode::FAILURE;
+    }
+    visited.push(exec_path.clone());
+
+    let visited_value = match env::join_paths(visited.iter()) {
         Ok(value) => value,
         Err(error) => {
-            println!("{}", error);
+            log_error!("{}", error);
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
     };
 
-    print_dbg_on_debug!(working_path.to_string_lossy());
-    print_dbg_on_debug!(exec_path.to_string_lossy());
+    // Runner is copied on platforms other than Linux, so a copy running now is the runner to compare as well.
+    let runner_path = env::current_exe().ok()
+        .and_then(| value | safe_canonicalize(&value).ok());
+    let config_resolver = ConfigResolver::default();
+
+    // Index may be narrowed down too much to explain errors, so config is resolved fully on any failure.
+    let index = config_resolver.resolve_index();
+    let indexed_config = index.as_ref()
+        .and_then(| value | value.config_for(&exec_path))
+        .map(| value | value.interpolated())
+        .filter(| (_, errors) | errors.is_empty())
+        .map(| (value, _) | value);
+    let indexed = indexed_config.as_ref()
+        .and_then(| value | select(&config_resolver, value, &exec_path, &working_path, &package_override).ok());
 
-    let package =
-        match config_resolver.package_resolver.find_by_paths(&config, &exec_path, &working_path)
-        {
-            None => {
-                println!("{}", "couldn't find runnable package.".bright_red());
+    let config: RuntimeConfig;
+    let (package, binder, overridden, mut runner_paths) = match (indexed, &index) {
+        (Some((value, value2, value3)), Some(value4)) => (value, value2, value3, value4.runner_paths()),
+        _ => {
+            print_dbg_on_debug!("resolving config fully.");
+
+            let (interpolated, errors) = match config_resolver.resolve() {
+                Ok(value) => value.interpolated(),
+                Err(error) => {
+                    log_error!("{}", error);
+                    pause_project_for_debug();
+
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if !errors.is_empty() {
+                for (value, error) in errors.iter() {
+                    log_error!("{}: {}", value, error.error_type);
+                }
+                log_error!("couldn't resolve references in config. Run validate to find every invalid one.");
                 pause_project_for_debug();
 
                 return ExitCode::FAILURE;
             }
-            Some(value) => value,
-        };
-    let binder = match package.find_binder_by_path(&exec_path) {
-        None => {
-            println!(
-                "{}",
-                "couldn't find bound executable in runnable package.".bright_red()
-            );
-            pause_project_for_debug();
 
-            return ExitCode::FAILURE;
+            config = interpolated;
+
+            match select(&config_resolver, &config, &exec_path, &working_path, &package_override) {
+                Ok((value, value2, value3)) => (value, value2, value3, runner_paths(&config, runner_path.as_ref())),
+                Err(error) => {
+                    for value in error.iter() {
+                        log_error!("{}", value);
+                    }
+                    pause_project_for_debug();
+
+                    return ExitCode::FAILURE;
+                }
+            }
         }
-        Some(value) => value,
     };
 
-    print_dbg_on_debug!(binder.convert_target_to_pathbuf());
+    let target_path = binder.convert_target_to_pathbuf();
+    print_dbg_on_debug!(target_path);
+
+    match runner_path {
+        Some(value) if !runner_paths.contains(&value) => runner_paths.push(value),
+        _ => {}
+    }
+
+    if resolves_to_runner(&target_path, &runner_paths) {
+        log_error!("target {} resolves to runner, so it would execute itself forever.", target_path.to_string_lossy());
+        log_error!("Bind the actual executable instead, and run validate to find such binders.");
+        pause_project_for_debug();
+
+        return ExitCode::FAILURE;
+    }
+
+    if overridden {
+        args.remove(0);
+    }
+
+    let mut _command = Command::new(&target_path);
+    let mut command = _command.args(args)
+        .env(RUNNER_DEPTH_ENV_KEY, (depth + 1).to_string())
+        .env(RUNNER_VISITED_ENV_KEY, visited_value)
+        .env(RUNNER_PROCESS_ENV_KEY, process::id().to_string());
+
+    for (key, value) in package.envs.iter() {
+        command = match value.apply(env::var(key).ok()) {
+            Some(value2) => command.env(key, value2),
+            None => command.env_remove(key),
+        };
+    }
 
-    let mut _command = Command::new(binder.convert_target_to_pathbuf());
-    let mut command = _command.args(args);
+    run_command(command)
+}
 
-    if !package.envs.is_empty() {
-        let mut vars: HashMap<String, String> = env::vars().collect();
+/**
+   Select package and binder by +<package-name>, env override or paths in order.
+   Returns whether +<package-name> selected the package, or lines of error to log when nothing is selected.
+**/
+fn select<'t>(
+    config_resolver: &ConfigResolver,
+    config: &'t RuntimeConfig,
+    exec_path: &'t Path,
+    working_path: &'t Path,
+    package_override: &Option<String>,
+) -> Result<(&'t RunnablePackage, &'t TargetBinder, bool), Vec<String>> {
+    if let Some(value) = package_override {
+        match config_resolver.package_resolver.find_by_override(config, exec_path, value) {
+            Ok(value2) => {
+                return Ok((value2.package, value2.binder, true));
+            }
+            Err(error) => {
+                // Only a package of the category is taken, since args like `vim +10` belong to the target.
+                if let BinderNotInPackage(..) = error.error_type {
+                    return Err(vec![
+                        error.to_string(),
+                        format!("couldn't select package by +{}.", value),
+                    ]);
+                }
 
-        for (key, value) in package.envs.iter() {
-            vars.insert(key.to_string(), value.to_string());
+                print_dbg_on_debug!(error.to_string());
+            }
         }
+    }
+
+    match config_resolver.package_resolver.find_by_env_override(config, exec_path) {
+        Ok(Some((value, applied))) => {
+            print_dbg_on_debug!(applied);
 
-        command = command.envs(&vars)
+            return Ok((value.package, value.binder, false));
+        }
+        Ok(None) => {}
+        Err(error) => {
+            return Err(vec![
+                error.to_string(),
+                format!("couldn't select package by env. Unset it or name a package of the category."),
+            ]);
+        }
     }
 
+    let evaluation = config_resolver.package_resolver.evaluate_by_paths(config, exec_path, working_path);
+
+    for value in evaluation.project_selection_errors.iter() {
+        log_warn!("{}", value);
+    }
+
+    match evaluation.selected {
+        None => Err(vec![format!("couldn't find runnable package.")]),
+        Some((value, _)) => Ok((value.package, value.binder, false)),
+    }
+}
+
+/**
+   Package name of leading "+<package-name>" like `rustup +toolchain`.
+   It's taken out of args only when it names a package of the category, so that it isn't passed to the target.
+**/
+fn package_override(args: &[String]) -> Option<String> {
+    match args.first() {
+        Some(value) if value.len() > 1 && value.starts_with('+') => Some(value[1..].to_string()),
+        _ => None,
+    }
+}
+
+fn runner_depth() -> u32 {
+    env::var(RUNNER_DEPTH_ENV_KEY).ok()
+        .and_then(| value | value.parse::<u32>().ok())
+        .unwrap_or(0)
+}
+
+/**
+   Exec paths visited by runners replaced in current process.
+   It stops an exec loop at its first repeat. Target started as a child process runs runners afresh,
+   so nested runs like recursive builds aren't mistaken as loops, and loops through child processes stop at the depth limit.
+**/
+fn visited_exec_paths() -> Vec<PathBuf> {
+    let same_process = env::var(RUNNER_PROCESS_ENV_KEY).ok()
+        .map(| value | value.eq(&process::id().to_string()))
+        .unwrap_or(false);
+
+    if !same_process {
+        return vec![];
+    }
+
+    match env::var_os(RUNNER_VISITED_ENV_KEY) {
+        None => vec![],
+        Some(value) => env::split_paths(&value).collect(),
+    }
+}
+
+/**
+   Replaces current process with the target, so that signals are delivered to the target directly
+   and its exit status becomes the status of the runner.
+**/
+#[cfg(unix)]
+fn run_command(command: &mut Command) -> ExitCode {
+    use std::os::unix::process::CommandExt;
+
+    let error = command.exec();
+
+    log_error!("{}", error);
+    log_error!("couldn't execute command successfully. (exec)");
+    pause_project_for_debug();
+
+    ExitCode::FAILURE
+}
+
+/**
+   Waits for the target and exits with its exit code.
+   Ctrl+C is delivered to every process attached to the console, so the target receives it as well.
+**/
+#[cfg(not(unix))]
+fn run_command(command: &mut Command) -> ExitCode {
     let mut _process = command.spawn();
     let process = match &mut _process {
         Ok(value) => value,
         Err(e) => {
-            println!("{}", e);
-            println!("couldn't execute command successfully. (spawn)");
+            log_error!("{}", e);
+            log_error!("couldn't execute command successfully. (spawn)");
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
     };
 
-    match process.wait() {
-        Ok(..) => {}
+    let status = match process.wait() {
+        Ok(value) => value,
         Err(e) => {
-            println!("{}", e);
-            println!("couldn't execute command successfully. (status)");
+            log_error!("{}", e);
+            log_error!("couldn't execute command successfully. (status)");
             pause_project_for_debug();
 
             return ExitCode::FAILURE;
         }
-    }
+    };
 
     pause_project_for_debug();
 
-    ExitCode::SUCCESS
+    match status.code() {
+        None => ExitCode::FAILURE,
+        Some(value) => std::process::exit(value),
+    }
 }
@@ -5,6 +5,10 @@ impl Argument {
         self.optional.insert(key, value)
     }
 
+    pub fn remove_optional(&mut self, key: String) -> Option<Option<String>> {
+        self.optional.remove(&key)
+    }
+
     pub fn optional_flag(&self, key: String) -> bool {
         self.optional.get(&key).is_some()
     }
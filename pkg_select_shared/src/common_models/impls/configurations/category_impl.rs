@@ -1,11 +1,14 @@
 use crate::common_models::models::configurations::package_category::PackageCategory;
 use crate::common_models::models::errors::validation::Type::{
-    EmptyPackageList, InvalidDefaultPackage,
+    DefaultPackageNotFound, EmptyPackageList, IncludedPathInSiblingPackage, InvalidDefaultPackage,
 };
-use crate::common_models::models::errors::validation::ValidationError;
+use crate::common_models::impls::configurations::package_impl::canonical_keys;
+use crate::common_models::models::errors::validation::ValidationReport;
+use crate::common_models::impls::errors::validation_impl::child_location;
 use crate::common_models::models::errors::validation_combo::IOCanonicalSerdeValidationError;
 use crate::common_models::traits::validator::Validator;
 use std::fmt::{Display, Formatter};
+use crate::PACKAGE_OVERRIDE_ENV_PREFIX;
 
 impl PartialEq for PackageCategory {
     fn eq(&self, other: &Self) -> bool {
@@ -15,13 +18,37 @@ impl PartialEq for PackageCategory {
     }
 }
 
+impl PackageCategory {
+    /**
+       Env var overriding selected package of this category, such as PKG_SELECT_JDK for category "jdk".
+    **/
+    pub fn override_env_key(&self) -> String {
+        let name: String = self.name.chars()
+            .map(| value | match value.is_ascii_alphanumeric() {
+                true => value.to_ascii_uppercase(),
+                false => '_',
+            })
+            .collect();
+
+        format!("{}{}", PACKAGE_OVERRIDE_ENV_PREFIX, name)
+    }
+}
+
 impl Validator for PackageCategory {
     fn validated(&self) -> bool {
         self.validatable.validated
     }
 
-    fn validate(&mut self) -> Result<(), IOCanonicalSerdeValidationError> {
-        validate_internal(self)
+    fn validate_into(&mut self, location: &str, report: &mut ValidationReport) -> Result<(), IOCanonicalSerdeValidationError> {
+        validate_internal(self, location, report)
+    }
+
+    fn warn_into(&self, location: &str, report: &mut ValidationReport) {
+        warn_internal(self, location, report)
+    }
+
+    fn location(&self) -> String {
+        format!("categories[{}]", self.name)
     }
 
     fn invalidate(&mut self) {
@@ -29,11 +56,15 @@ impl Validator for PackageCategory {
     }
 }
 
-fn validate_internal(category: &mut PackageCategory) -> Result<(), IOCanonicalSerdeValidationError> {
+fn validate_internal(
+    category: &mut PackageCategory,
+    location: &str,
+    report: &mut ValidationReport,
+) -> Result<(), IOCanonicalSerdeValidationError> {
+    let error_count = report.errors().len();
+
     if category.packages.is_empty() {
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: EmptyPackageList,
-        }));
+        report.error(&child_location(location, "packages"), EmptyPackageList);
     }
 
     for value in category.packages.iter_mut() {
@@ -41,42 +72,55 @@ fn validate_internal(category: &mut PackageCategory) -> Result<(), IOCanonicalSe
             continue;
         }
 
-        match value.validate() {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(error);
-            }
-        }
+        let package_location = child_location(location, &value.location());
+        value.validate_into(&package_location, report)?;
     }
 
-    let default_package_index = match category.default_package {
+    match &category.default_package {
         None => {
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: InvalidDefaultPackage,
-            }));
+            report.error(&child_location(location, "default_package"), InvalidDefaultPackage);
         }
-        Some(value) => value,
-    };
-
-    if default_package_index >= category.packages.len() {
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: InvalidDefaultPackage,
-        }));
-    }
-
-    match category.packages.get(default_package_index) {
-        None => {
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: InvalidDefaultPackage,
-            }));
+        Some(value) => {
+            if !category.packages.iter().any(| value2 | value2.name.eq(value)) {
+                report.error(&child_location(location, "default_package"), DefaultPackageNotFound(value.to_string()));
+            }
         }
-        Some(_) => {}
     }
 
-    category.validatable.validated = true;
+    category.validatable.validated = report.errors().len() == error_count;
     Ok(())
 }
 
+fn warn_internal(category: &PackageCategory, location: &str, report: &mut ValidationReport) {
+    let mut included_paths: Vec<(String, &String)> = vec![];
+
+    for value in category.packages.iter() {
+        let package_location = child_location(location, &value.location());
+        value.warn_into(&package_location, report);
+
+        let keys = canonical_keys(&value.interpolated().0.included_paths);
+
+        for (index, value2) in keys.iter().enumerate() {
+            let sibling = included_paths.iter()
+                .find(| (value3, value4) | value3.eq(value2) && !(*value4).eq(&value.name));
+
+            match sibling {
+                None => {}
+                Some((_, value3)) => {
+                    report.warning(
+                        &child_location(&package_location, &format!("included_paths[{}]", index)),
+                        IncludedPathInSiblingPackage(value2.to_string(), value3.to_string()),
+                    );
+                }
+            }
+        }
+
+        for value2 in keys.into_iter() {
+            included_paths.push((value2, &value.name));
+        }
+    }
+}
+
 fn invalidate(category: &mut PackageCategory) {
     for value in category.packages.iter_mut() {
         value.invalidate();
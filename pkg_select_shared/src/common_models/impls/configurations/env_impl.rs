@@ -0,0 +1,156 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::common_models::models::configurations::env_var::{EnvOperation, EnvValue};
+
+impl EnvOperation {
+    pub fn all() -> Vec<EnvOperation> {
+        vec![
+            EnvOperation::Set,
+            EnvOperation::Prepend,
+            EnvOperation::Append,
+            EnvOperation::SetIfUnset,
+            EnvOperation::Unset,
+        ]
+    }
+
+    pub fn from_name(name: &str) -> Option<EnvOperation> {
+        EnvOperation::all().into_iter()
+            .find(| value | value.to_string().eq(name))
+    }
+}
+
+impl fmt::Display for EnvOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvOperation::Set => write!(f, "set"),
+            EnvOperation::Prepend => write!(f, "prepend"),
+            EnvOperation::Append => write!(f, "append"),
+            EnvOperation::SetIfUnset => write!(f, "set-if-unset"),
+            EnvOperation::Unset => write!(f, "unset"),
+        }
+    }
+}
+
+impl EnvValue {
+    pub fn set(value: &str) -> EnvValue {
+        EnvValue {
+            operation: EnvOperation::Set,
+            value: value.to_string(),
+            separator: None,
+        }
+    }
+
+    pub fn separator(&self) -> String {
+        match &self.separator {
+            Some(value) => value.to_string(),
+            None => match cfg!(windows) {
+                true => ";".to_string(),
+                false => ":".to_string(),
+            },
+        }
+    }
+
+    /**
+       Compose the value from the one inherited by runners.
+       Returns nothing when the env var must be removed.
+    **/
+    pub fn apply(&self, current: Option<String>) -> Option<String> {
+        let current = current.filter(| value | !value.is_empty());
+
+        match self.operation {
+            EnvOperation::Set => Some(self.value.to_string()),
+            EnvOperation::Prepend => match current {
+                None => Some(self.value.to_string()),
+                Some(value) => Some(format!("{}{}{}", self.value, self.separator(), value)),
+            },
+            EnvOperation::Append => match current {
+                None => Some(self.value.to_string()),
+                Some(value) => Some(format!("{}{}{}", value, self.separator(), self.value)),
+            },
+            EnvOperation::SetIfUnset => Some(current.unwrap_or(self.value.to_string())),
+            EnvOperation::Unset => None,
+        }
+    }
+}
+
+impl fmt::Display for EnvValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.operation {
+            EnvOperation::Set => write!(f, "{}", self.value),
+            EnvOperation::Unset => write!(f, "({})", self.operation),
+            EnvOperation::SetIfUnset => write!(f, "{} ({})", self.value, self.operation),
+            EnvOperation::Prepend | EnvOperation::Append => {
+                write!(f, "{} ({} with \"{}\")", self.value, self.operation, self.separator())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_value(operation: EnvOperation, value: &str, separator: Option<&str>) -> EnvValue {
+        EnvValue {
+            operation,
+            value: value.to_string(),
+            separator: separator.map(| value2 | value2.to_string()),
+        }
+    }
+
+    #[test]
+    fn operation_is_found_by_its_name() {
+        for value in EnvOperation::all() {
+            assert_eq!(EnvOperation::from_name(&value.to_string()), Some(value));
+        }
+        assert_eq!(EnvOperation::from_name("set-if-unset"), Some(EnvOperation::SetIfUnset));
+        assert_eq!(EnvOperation::from_name("replace"), None);
+    }
+
+    #[test]
+    fn set_and_unset_ignore_current_value() {
+        let current = Some("/usr/bin".to_string());
+
+        assert_eq!(EnvValue::set("/opt/bin").apply(current.clone()), Some("/opt/bin".to_string()));
+        assert_eq!(env_value(EnvOperation::Unset, "", None).apply(current), None);
+    }
+
+    #[test]
+    fn prepend_and_append_join_current_value_with_separator() {
+        let current = Some("/usr/bin".to_string());
+        let prepend = env_value(EnvOperation::Prepend, "/opt/bin", Some(","));
+        let append = env_value(EnvOperation::Append, "/opt/bin", Some(","));
+
+        assert_eq!(prepend.apply(current.clone()), Some("/opt/bin,/usr/bin".to_string()));
+        assert_eq!(append.apply(current), Some("/usr/bin,/opt/bin".to_string()));
+    }
+
+    #[test]
+    fn prepend_and_append_to_unset_or_empty_value_skip_separator() {
+        let prepend = env_value(EnvOperation::Prepend, "/opt/bin", None);
+        let append = env_value(EnvOperation::Append, "/opt/bin", None);
+
+        assert_eq!(prepend.apply(None), Some("/opt/bin".to_string()));
+        assert_eq!(append.apply(Some(String::new())), Some("/opt/bin".to_string()));
+    }
+
+    #[test]
+    fn set_if_unset_keeps_non_empty_current_value() {
+        let value = env_value(EnvOperation::SetIfUnset, "/opt/jdk", None);
+
+        assert_eq!(value.apply(Some("/usr/jdk".to_string())), Some("/usr/jdk".to_string()));
+        assert_eq!(value.apply(Some(String::new())), Some("/opt/jdk".to_string()));
+        assert_eq!(value.apply(None), Some("/opt/jdk".to_string()));
+    }
+
+    #[test]
+    fn separator_defaults_to_path_list_separator_of_platform() {
+        let expected = match cfg!(windows) {
+            true => ";",
+            false => ":",
+        };
+
+        assert_eq!(env_value(EnvOperation::Append, "", None).separator(), expected);
+        assert_eq!(env_value(EnvOperation::Append, "", Some(" ")).separator(), " ");
+    }
+}
@@ -1,3 +1,4 @@
 pub mod binder_impl;
 pub mod category_impl;
+pub mod env_impl;
 pub mod package_impl;
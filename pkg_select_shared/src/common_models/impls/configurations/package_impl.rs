@@ -1,19 +1,22 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use crate::common_models::models::configurations::env_var::{EnvOperation, EnvValue};
 use crate::common_models::models::configurations::runnable_package::RunnablePackage;
 use crate::common_models::models::configurations::target_binder::TargetBinder;
-use crate::common_models::models::errors::validation::Type::{DuplicatedBinderExecutionPath, DuplicatedExcludedPath, DuplicatedIncludedPath, DuplicatedPathInIncludedAndExcluded, EmptyBinderList, InvalidBinderPath, InvalidExcludedPath, InvalidIncludedPath};
-use crate::common_models::models::errors::validation::ValidationError;
+use crate::common_models::models::errors::validation::Type::{DuplicatedBinderExecutionPath, DuplicatedExcludedPath, DuplicatedIncludedPath, DuplicatedPathInIncludedAndExcluded, EmptyBinderList, EnvPathNotFound, ExecutionPathIsTargetPath, InvalidBinderPath, InvalidExcludedPath, InvalidIncludedPath, InvalidReference, TargetNotExecutable, UndefinedEnvReference};
+use crate::common_models::models::errors::validation::ValidationReport;
+use crate::common_models::impls::errors::validation_impl::child_location;
+use crate::common_models::impls::interpolation_impl::refers_to_env;
 use crate::common_models::models::errors::validation_combo::IOCanonicalSerdeValidationError;
 use crate::common_models::traits::binder_search::BinderSearch;
 use crate::common_models::traits::validator::Validator;
-use crate::{PathPop, safe_canonicalize};
-use crate::common_models::models::errors::canonical_path_combo::IOCanonicalError;
+use crate::{is_executable_file, is_path_pattern, PathPop, safe_canonicalize};
 use crate::common_models::traits::binder_converter::BinderConverter;
 
 impl PartialEq for RunnablePackage {
     fn eq(&self, other: &Self) -> bool {
         self.name.eq(&other.name) &&
+            self.home.eq(&other.home) &&
             self.binders.eq(&other.binders) &&
             self.excluded_paths.eq(&other.excluded_paths) &&
             self.included_paths.eq(&other.included_paths) &&
@@ -26,8 +29,16 @@ impl Validator for RunnablePackage {
         self.validatable.validated
     }
 
-    fn validate(&mut self) -> Result<(), IOCanonicalSerdeValidationError> {
-        validate_internal(self)
+    fn validate_into(&mut self, location: &str, report: &mut ValidationReport) -> Result<(), IOCanonicalSerdeValidationError> {
+        validate_internal(self, location, report)
+    }
+
+    fn warn_into(&self, location: &str, report: &mut ValidationReport) {
+        warn_internal(self, location, report)
+    }
+
+    fn location(&self) -> String {
+        format!("packages[{}]", self.name)
     }
 
     fn invalidate(&mut self) {
@@ -35,98 +46,143 @@ impl Validator for RunnablePackage {
     }
 }
 
-fn validate_internal(package: &mut RunnablePackage) -> Result<(), IOCanonicalSerdeValidationError> {
-    if package.binders.is_empty() {
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: EmptyBinderList,
-        }));
+fn validate_internal(
+    package: &mut RunnablePackage,
+    location: &str,
+    report: &mut ValidationReport,
+) -> Result<(), IOCanonicalSerdeValidationError> {
+    let error_count = report.errors().len();
+    let (interpolated, interpolation_errors) = package.interpolated();
+
+    for (value, error) in interpolation_errors.into_iter() {
+        report.error(&child_location(location, &value), InvalidReference(error));
     }
 
-    for value in package.binders.iter() {
-        if !validate_binder_path(value) {
-            package.validatable.validated = false;
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: InvalidBinderPath,
-            }));
+    if interpolated.binders.is_empty() {
+        report.error(&child_location(location, "binders"), EmptyBinderList);
+    }
+
+    // Unset env makes target empty only until the runner runs with it.
+    for (index, value) in interpolated.binders.iter().enumerate() {
+        let runtime_target = refers_to_env(&package.binders[index].target_path);
+
+        if !(validate_binder_path(value) || (runtime_target && validate_binder_path(&package.binders[index]))) {
+            report.error(&child_location(location, &format!("binders[{}]", index)), InvalidBinderPath);
         }
     }
-    let binder_exec_path_set: Vec<String> = package.binders.iter()
-        .map(| value | value.convert_exec_to_pathbuf().to_string_lossy().to_string())
+
+    let binder_exec_paths: Vec<String> = interpolated.binders.iter()
+        .map(| value | canonical_exec_key(&value.convert_exec_to_pathbuf()))
         .collect();
-    let binder_validated = match validate_paths_unique(&binder_exec_path_set) {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::IOCanonicalError(error));
-        }
-    };
-    if !binder_validated {
-        package.validatable.validated = false;
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: DuplicatedBinderExecutionPath,
-        }));
-    }
-
-    for value in package.included_paths.iter() {
-        if !validate_path(value) {
-            package.validatable.validated = false;
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: InvalidIncludedPath,
-            }));
+    for (index, value) in find_duplicated_paths(&binder_exec_paths).iter() {
+        report.error(
+            &child_location(location, &format!("binders[{}]", index)),
+            DuplicatedBinderExecutionPath(value.to_string()),
+        );
+    }
+
+    // Paths referring to env are resolved by the runner, so they are checked only for duplication.
+    for (index, value) in interpolated.included_paths.iter().enumerate() {
+        if !refers_to_env(&package.included_paths[index]) && !validate_path(value) {
+            report.error(
+                &child_location(location, &format!("included_paths[{}]", index)),
+                InvalidIncludedPath(value.to_string()),
+            );
         }
     }
-    let included_paths_validated = match validate_paths_unique(&package.included_paths) {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::IOCanonicalError(error));
+    for (index, value) in find_duplicated_paths(&canonical_keys(&interpolated.included_paths)).iter() {
+        report.error(
+            &child_location(location, &format!("included_paths[{}]", index)),
+            DuplicatedIncludedPath(value.to_string()),
+        );
+    }
+
+    for (index, value) in interpolated.excluded_paths.iter().enumerate() {
+        if !refers_to_env(&package.excluded_paths[index]) && !validate_path(value) {
+            report.error(
+                &child_location(location, &format!("excluded_paths[{}]", index)),
+                InvalidExcludedPath(value.to_string()),
+            );
         }
-    };
-    if !included_paths_validated {
-        package.validatable.validated = false;
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: DuplicatedIncludedPath,
-        }));
-    }
-
-    for value in package.excluded_paths.iter() {
-        if !validate_path(value) {
-            package.validatable.validated = false;
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: InvalidExcludedPath,
-            }));
+    }
+    for (index, value) in find_duplicated_paths(&canonical_keys(&interpolated.excluded_paths)).iter() {
+        report.error(
+            &child_location(location, &format!("excluded_paths[{}]", index)),
+            DuplicatedExcludedPath(value.to_string()),
+        );
+    }
+
+    let included_keys = canonical_keys(&interpolated.included_paths);
+    for (index, value) in canonical_keys(&interpolated.excluded_paths).iter().enumerate() {
+        if included_keys.contains(value) {
+            report.error(
+                &child_location(location, &format!("excluded_paths[{}]", index)),
+                DuplicatedPathInIncludedAndExcluded(value.to_string()),
+            );
         }
     }
-    let excluded_paths_validated = match validate_paths_unique(&package.excluded_paths) {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::IOCanonicalError(error));
+
+    package.validatable.validated = report.errors().len() == error_count;
+    Ok(())
+}
+
+fn warn_internal(package: &RunnablePackage, location: &str, report: &mut ValidationReport) {
+    for (value, value2) in package.undefined_env_references().into_iter() {
+        report.warning(&child_location(location, &value), UndefinedEnvReference(value2));
+    }
+
+    let (interpolated, _) = package.interpolated();
+
+    for (index, value) in interpolated.binders.iter().enumerate() {
+        if !validate_binder_path(value) {
+            continue;
+        }
+
+        let binder_location = child_location(location, &format!("binders[{}]", index));
+        let target_path = value.convert_target_to_pathbuf();
+        let runtime_target = refers_to_env(&package.binders[index].target_path);
+
+        if !runtime_target && target_path.exists() && !is_executable_file(&target_path) {
+            report.warning(&binder_location, TargetNotExecutable(target_path.to_string_lossy().to_string()));
+        }
+
+        let target_dir = canonical_keys(&[value.target_path.to_string()]);
+        let exec_dir = canonical_keys(&[value.execution_path.to_string()]);
+
+        if target_dir.eq(&exec_dir) {
+            report.warning(&binder_location, ExecutionPathIsTargetPath(value.execution_path.to_string()));
         }
-    };
-    if !excluded_paths_validated {
-        package.validatable.validated = false;
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: DuplicatedExcludedPath,
-        }));
     }
 
-    let working_paths: Vec<String> = (&package.included_paths).iter()
-        .chain((&package.excluded_paths).iter())
-        .map(| value | value.to_string())
+    let mut envs: Vec<(&String, &EnvValue)> = interpolated.envs.iter()
+        .filter(| (_, value) | value.operation != EnvOperation::Unset)
+        .filter(| (key, _) | !package.envs.get(*key).map(| value | refers_to_env(&value.value)).unwrap_or(false))
         .collect();
-    let working_paths_validated = match validate_paths_unique(&working_paths) {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::IOCanonicalError(error));
+    envs.sort_by(| value, value2 | value.0.cmp(value2.0));
+
+    for (key, value) in envs.iter() {
+        for value2 in missing_env_paths(&value.value).iter() {
+            report.warning(
+                &child_location(location, &format!("envs[{}]", key)),
+                EnvPathNotFound(key.to_string(), value2.to_string()),
+            );
         }
-    };
-    if !working_paths_validated {
-        package.validatable.validated = false;
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: DuplicatedPathInIncludedAndExcluded,
-        }));
     }
+}
 
-    package.validatable.validated = true;
-    Ok(())
+/**
+   Env value is treated as path only when it is absolute, and it may be a list like PATH.
+   Values like URL are skipped since they can't be told apart from path lists.
+**/
+fn missing_env_paths(value: &String) -> Vec<String> {
+    if value.contains("://") {
+        return vec![];
+    }
+
+    std::env::split_paths(value)
+        .filter(| value2 | value2.is_absolute() && !value2.exists())
+        .map(| value2 | value2.to_string_lossy().to_string())
+        .collect()
 }
 
 fn validate_binder_path(binder: &TargetBinder) -> bool {
@@ -141,30 +197,66 @@ fn validate_binder_path(binder: &TargetBinder) -> bool {
 }
 
 fn validate_path(path: &String) -> bool {
-    Path::new(path).exists()
+    match is_path_pattern(path) {
+        true => Path::new(path).is_absolute(),
+        false => Path::new(path).exists(),
+    }
 }
 
-fn validate_paths_unique(list: &Vec<String>) -> Result<bool, IOCanonicalError> {
-    let paths: Result<HashSet<String>, IOCanonicalError> = list.iter()
+pub(crate) fn canonical_keys(list: &[String]) -> Vec<String> {
+    list.iter()
         .map(| value | {
+            if is_path_pattern(value) {
+                return value.to_string();
+            }
+
             safe_canonicalize(&PathBuf::from(value))
                 .map(| value | value.to_string_lossy().to_string())
+                .unwrap_or(value.to_string())
         })
-        .collect();
+        .collect()
+}
+
+/**
+   Execution path is often a runner symlink, so only its directory is canonicalized.
+   Otherwise every registered binder would resolve to the runner itself.
+**/
+fn canonical_exec_key(path: &Path) -> String {
+    let canonical_dir = path.parent()
+        .and_then(| value | safe_canonicalize(value).ok());
+
+    match (canonical_dir, path.file_name()) {
+        (Some(value), Some(value2)) => value.join(value2).to_string_lossy().to_string(),
+        _ => path.to_string_lossy().to_string(),
+    }
+}
+
+/**
+   Returns index and value of every entry appeared before.
+**/
+fn find_duplicated_paths(list: &[String]) -> Vec<(usize, String)> {
+    let mut paths: HashSet<&String> = HashSet::new();
+    let mut duplicated: Vec<(usize, String)> = vec![];
+
+    for (index, value) in list.iter().enumerate() {
+        if !paths.insert(value) {
+            duplicated.push((index, value.to_string()));
+        }
+    }
 
-    Ok(list.len() == paths?.len())
+    duplicated
 }
 
 impl BinderSearch for RunnablePackage {
     fn find_binder_by_path<'t>(&'t self, path: &'t PathBuf) -> Option<&'t TargetBinder> {
-        let safe_path = safe_canonicalize(path).ok()?;
-        let filename = match (&safe_path).is_file() {
-            true => (&safe_path).file_name()?,
+        // Execution path is a runner symlink on Linux, so it is compared as is like finding packages.
+        let filename = match path.is_file() {
+            true => path.file_name()?,
             false => {
                 return None;
             }
         };
-        let path_without_filename = (&safe_path).pop_path();
+        let path_without_filename = path.pop_path();
 
         self.binders.iter().find(|value| {
             let execution_path = Path::new(&value.execution_path);
@@ -9,6 +9,7 @@ impl fmt::Display for SerdeIODirectoryError {
             SerdeIODirectoryError::DirectoryResolveError(value) => value.fmt(f),
             SerdeIODirectoryError::SerdeError(value) => value.fmt(f),
             SerdeIODirectoryError::IOError(value) => value.fmt(f),
+            SerdeIODirectoryError::SchemaMigrationError(value) => value.fmt(f),
         }
     }
 }
@@ -0,0 +1,31 @@
+use crate::common_models::models::errors::interpolation::{InterpolationError, Type};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::UnknownReference(value) => {
+                write!(f, "unknown reference ${{{}}}", value)
+            }
+            Type::UnclosedReference(value) => {
+                write!(f, "reference in {} is not closed", value)
+            }
+            Type::PackageHomeNotSet => {
+                write!(f, "${{PACKAGE_HOME}} is referenced but home of package is not set")
+            }
+            Type::HomeNotFound => {
+                write!(f, "couldn't find home directory for ${{HOME}}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for InterpolationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Interpolation Error: {}", self.error_type)
+    }
+}
+
+impl Error for InterpolationError {}
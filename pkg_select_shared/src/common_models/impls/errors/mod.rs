@@ -2,5 +2,9 @@ pub mod canonical_path_combo_impl;
 pub mod canonical_path_impl;
 pub mod config_resolve_combo_impl;
 pub mod directory_resolve_impl;
+pub mod interpolation_impl;
+pub mod package_override_impl;
+pub mod project_selection_impl;
+pub mod schema_migration_impl;
 pub mod validation_combo_impl;
 pub mod validation_impl;
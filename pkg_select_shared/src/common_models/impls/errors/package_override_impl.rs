@@ -0,0 +1,34 @@
+use crate::common_models::models::errors::package_override::{PackageOverrideError, Type};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::BinderNotFound(value) => {
+                write!(f, "no binder is bound to {}", value)
+            }
+            Type::PackageNotFound(value) => {
+                write!(f, "package {} doesn't exist", value)
+            }
+            Type::PackageNotInCategory(value, value2) => {
+                write!(f, "package {} doesn't belong to category {} which owns the binder", value, value2)
+            }
+            Type::BinderNotInPackage(value, value2) => {
+                write!(f, "package {} has no binder named {}", value, value2)
+            }
+            Type::InvalidEnvOverride(value, value2) => {
+                write!(f, "env {} is invalid: {}", value, value2)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PackageOverrideError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Package Override Error: {}", self.error_type)
+    }
+}
+
+impl Error for PackageOverrideError {}
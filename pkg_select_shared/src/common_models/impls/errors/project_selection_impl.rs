@@ -0,0 +1,28 @@
+use crate::common_models::models::errors::project_selection::{ProjectSelectionError, Type};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Unreadable(path, reason) => {
+                write!(f, "couldn't read {}, so it is ignored. ({})", path, reason)
+            }
+            Type::Malformed(path, reason) => {
+                write!(f, "{} is ignored since it is not an object of category and package names. ({})", path, reason)
+            }
+            Type::UnknownPackage(path, category, package) => {
+                write!(f, "{} is ignored for category {} since it names unknown package {}", path, category, package)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ProjectSelectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Project Selection Error: {}", self.error_type)
+    }
+}
+
+impl Error for ProjectSelectionError {}
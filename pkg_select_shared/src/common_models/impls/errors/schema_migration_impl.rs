@@ -0,0 +1,34 @@
+use crate::common_models::models::errors::schema_migration::{SchemaMigrationError, Type};
+use crate::common_models::models::runtime_config::CURRENT_SCHEMA_VERSION;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::InvalidSchemaVersion => {
+                write!(f, "schema_version is not a valid version number")
+            }
+            Type::NewerSchemaVersion(value) => {
+                write!(
+                    f,
+                    "config has schema version {} but this build supports up to {}. Update pkg_select first",
+                    value,
+                    CURRENT_SCHEMA_VERSION
+                )
+            }
+            Type::MigrationFailed(value) => {
+                write!(f, "couldn't migrate config from schema version {}", value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SchemaMigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Schema Migration Error: {}", self.error_type)
+    }
+}
+
+impl Error for SchemaMigrationError {}
@@ -7,7 +7,7 @@ impl fmt::Display for IOCanonicalSerdeValidationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match &self {
             IOCanonicalSerdeValidationError::SerdeError(value) => value.fmt(f),
-            IOCanonicalSerdeValidationError::ValidationError(value) => value.fmt(f),
+            IOCanonicalSerdeValidationError::ValidationReport(value) => value.fmt(f),
             IOCanonicalSerdeValidationError::IOCanonicalError(value) => value.fmt(f),
         }
     }
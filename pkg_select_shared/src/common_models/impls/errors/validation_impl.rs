@@ -1,18 +1,156 @@
-use crate::common_models::models::errors::validation::{Type, ValidationError};
+use crate::common_models::models::errors::validation::{Severity, Type, ValidationError, ValidationReport};
 use std::error::Error;
 use std::fmt;
 use std::fmt::Formatter;
 
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Type::InvalidBinderPath => {
+                write!(f, "target name, target path and execution path of binder must not be empty.")
+            }
+            Type::InvalidIncludedPath(value) => {
+                write!(f, "included path {} doesn't exist or isn't absolute pattern.", value)
+            }
+            Type::InvalidExcludedPath(value) => {
+                write!(f, "excluded path {} doesn't exist or isn't absolute pattern.", value)
+            }
+            Type::InvalidDefaultPackage => {
+                write!(f, "default package is not set.")
+            }
+            Type::DefaultPackageNotFound(value) => {
+                write!(f, "default package {} doesn't exist in the category.", value)
+            }
+            Type::EmptyCategoryList => {
+                write!(f, "no category is defined.")
+            }
+            Type::EmptyPackageList => {
+                write!(f, "category has no package.")
+            }
+            Type::EmptyBinderList => {
+                write!(f, "package has no binder.")
+            }
+            Type::DuplicatedBinderExecutionPath(value) => {
+                write!(f, "execution path {} is used by another binder.", value)
+            }
+            Type::DuplicatedIncludedPath(value) => {
+                write!(f, "included path {} is duplicated.", value)
+            }
+            Type::DuplicatedExcludedPath(value) => {
+                write!(f, "excluded path {} is duplicated.", value)
+            }
+            Type::DuplicatedPathInIncludedAndExcluded(value) => {
+                write!(f, "path {} is both included and excluded.", value)
+            }
+            Type::NonUniqueName(value) => {
+                write!(f, "name {} is already used.", value)
+            }
+            Type::NonUniqueOverrideEnvKey(value, value2) => {
+                write!(f, "env {} overriding the category also overrides category {}.", value, value2)
+            }
+            Type::ReservedOverrideEnvKey(value) => {
+                write!(f, "env {} overriding the category is reserved by Package Select.", value)
+            }
+            Type::NonUniqueTargetPath(value) => {
+                write!(f, "target {} is already bound by another binder.", value)
+            }
+            Type::TargetNotFound(value) => {
+                write!(f, "target {} doesn't exist.", value)
+            }
+            Type::TargetNotExecutable(value) => {
+                write!(f, "target {} is not executable.", value)
+            }
+            Type::TargetIsRunner(value) => {
+                write!(f, "target {} resolves to runner or a runner registered for binders, so runner would execute itself.", value)
+            }
+            Type::ExecutionPathIsTargetPath(value) => {
+                write!(f, "execution path {} is the directory of target, so runner would execute itself.", value)
+            }
+            Type::IncludedPathInSiblingPackage(value, value2) => {
+                write!(f, "included path {} is also included by package {}.", value, value2)
+            }
+            Type::EnvPathNotFound(value, value2) => {
+                write!(f, "env {} points to {} which doesn't exist.", value, value2)
+            }
+            Type::InvalidReference(value) => {
+                write!(f, "{}.", value.error_type)
+            }
+            Type::UndefinedEnvReference(value) => {
+                write!(f, "${{env:{}}} is referenced but env {} is not set now. It's resolved when runner runs, so it would be empty unless it's set then.", value, value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
     }
 }
 
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Validation Error: {}", self.error_type)
+        write!(f, "{}: {}: {}", self.severity, self.location, self.error_type)
     }
 }
 
 impl Error for ValidationError {}
+
+impl ValidationReport {
+    pub fn error(&mut self, location: &str, error_type: Type) {
+        self.push(location, error_type, Severity::Error);
+    }
+
+    pub fn warning(&mut self, location: &str, error_type: Type) {
+        self.push(location, error_type, Severity::Warning);
+    }
+
+    pub fn errors(&self) -> Vec<&ValidationError> {
+        self.findings.iter()
+            .filter(| value | value.severity == Severity::Error)
+            .collect()
+    }
+
+    pub fn warnings(&self) -> Vec<&ValidationError> {
+        self.findings.iter()
+            .filter(| value | value.severity == Severity::Warning)
+            .collect()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(| value | value.severity == Severity::Error)
+    }
+
+    fn push(&mut self, location: &str, error_type: Type, severity: Severity) {
+        self.findings.push(ValidationError {
+            error_type,
+            severity,
+            location: location.to_string(),
+        });
+    }
+}
+
+/**
+   Join location of parent and child like categories[jdk] and packages[temurin-17].
+**/
+pub fn child_location(parent: &str, child: &str) -> String {
+    match parent.is_empty() {
+        true => child.to_string(),
+        false => format!("{}.{}", parent, child),
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.findings.iter()
+            .map(| value | value.to_string())
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Error for ValidationReport {}
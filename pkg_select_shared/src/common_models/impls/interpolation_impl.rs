@@ -0,0 +1,381 @@
+use std::env;
+use std::path::Path;
+use crate::common_models::impls::errors::validation_impl::child_location;
+use crate::common_models::models::configurations::package_category::PackageCategory;
+use crate::common_models::models::configurations::runnable_package::RunnablePackage;
+use crate::common_models::models::errors::interpolation::InterpolationError;
+use crate::common_models::models::errors::interpolation::Type::{HomeNotFound, PackageHomeNotSet, UnclosedReference, UnknownReference};
+use crate::common_models::models::runtime_config::RuntimeConfig;
+use crate::common_models::traits::validator::Validator;
+use crate::user_home_dir;
+
+const ENV_REFERENCE_PREFIX: &str = "env:";
+
+impl RunnablePackage {
+    /**
+       Copy with references resolved in home, envs, target paths of binders, included paths and excluded paths.
+       Relative target paths resolve against home as well.
+       Values which couldn't be resolved stay as they are, and they are returned with their locations.
+    **/
+    pub fn interpolated(&self) -> (RunnablePackage, Vec<(String, InterpolationError)>) {
+        let mut package = self.clone();
+        let mut errors: Vec<(String, InterpolationError)> = vec![];
+
+        // Home can't refer to itself, and it stays raw on failure so that its references aren't reported twice.
+        package.home = match &self.home {
+            None => None,
+            Some(value) => match interpolate(value, &self.name, None) {
+                Ok(value2) => Some(value2),
+                Err(error) => {
+                    errors.push(("home".to_string(), error));
+                    Some(value.to_string())
+                }
+            },
+        };
+
+        let home = package.home.clone();
+
+        for (key, value) in package.envs.iter_mut() {
+            match interpolate(&value.value, &self.name, home.as_ref()) {
+                Ok(value2) => value.value = value2,
+                Err(error) => errors.push((format!("envs[{}]", key), error)),
+            }
+        }
+
+        for (index, value) in package.binders.iter_mut().enumerate() {
+            match interpolate(&value.target_path, &self.name, home.as_ref()) {
+                Ok(value2) => value.target_path = value2,
+                Err(error) => {
+                    errors.push((format!("binders[{}]", index), error));
+                    continue;
+                }
+            }
+
+            match &home {
+                Some(value2) if !value.target_path.is_empty() && Path::new(&value.target_path).is_relative() => {
+                    value.target_path = Path::new(value2).join(&value.target_path).to_string_lossy().to_string();
+                }
+                _ => {}
+            }
+        }
+
+        for (index, value) in package.included_paths.iter_mut().enumerate() {
+            match interpolate(value, &self.name, home.as_ref()) {
+                Ok(value2) => *value = value2,
+                Err(error) => errors.push((format!("included_paths[{}]", index), error)),
+            }
+        }
+
+        for (index, value) in package.excluded_paths.iter_mut().enumerate() {
+            match interpolate(value, &self.name, home.as_ref()) {
+                Ok(value2) => *value = value2,
+                Err(error) => errors.push((format!("excluded_paths[{}]", index), error)),
+            }
+        }
+
+        (package, errors)
+    }
+
+    /**
+       Env vars referred by ${env:NAME} which are not set now, so they would become empty.
+    **/
+    pub fn undefined_env_references(&self) -> Vec<(String, String)> {
+        let mut values: Vec<(String, &String)> = vec![];
+
+        match &self.home {
+            None => {}
+            Some(value) => values.push(("home".to_string(), value)),
+        }
+
+        let mut envs: Vec<(&String, &String)> = self.envs.iter()
+            .map(| (key, value) | (key, &value.value))
+            .collect();
+        envs.sort();
+
+        for (key, value) in envs.into_iter() {
+            values.push((format!("envs[{}]", key), value));
+        }
+
+        for (index, value) in self.binders.iter().enumerate() {
+            values.push((format!("binders[{}]", index), &value.target_path));
+        }
+
+        for (index, value) in self.included_paths.iter().enumerate() {
+            values.push((format!("included_paths[{}]", index), value));
+        }
+
+        for (index, value) in self.excluded_paths.iter().enumerate() {
+            values.push((format!("excluded_paths[{}]", index), value));
+        }
+
+        let mut undefined: Vec<(String, String)> = vec![];
+
+        for (location, value) in values.into_iter() {
+            for value2 in references(value).into_iter() {
+                match value2.strip_prefix(ENV_REFERENCE_PREFIX) {
+                    Some(value3) if !value3.is_empty() && env::var_os(value3).is_none() => {
+                        undefined.push((location.to_string(), value3.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        undefined
+    }
+}
+
+impl PackageCategory {
+    /**
+       Copy with every package interpolated. See [RunnablePackage::interpolated].
+    **/
+    pub fn interpolated(&self) -> (PackageCategory, Vec<(String, InterpolationError)>) {
+        let mut category = self.clone();
+        let mut errors: Vec<(String, InterpolationError)> = vec![];
+
+        for value in category.packages.iter_mut() {
+            let (package, package_errors) = value.interpolated();
+
+            for (location, error) in package_errors.into_iter() {
+                errors.push((child_location(&value.location(), &location), error));
+            }
+
+            *value = package;
+        }
+
+        (category, errors)
+    }
+}
+
+impl RuntimeConfig {
+    /**
+       Copy with every package interpolated. See [RunnablePackage::interpolated].
+       Raw config must be kept for saving, since references are resolved only when config is used.
+    **/
+    pub fn interpolated(&self) -> (RuntimeConfig, Vec<(String, InterpolationError)>) {
+        let mut config = self.clone();
+        let mut errors: Vec<(String, InterpolationError)> = vec![];
+
+        for value in config.package_categories.iter_mut() {
+            let (category, category_errors) = value.interpolated();
+
+            for (location, error) in category_errors.into_iter() {
+                errors.push((child_location(&value.location(), &location), error));
+            }
+
+            *value = category;
+        }
+
+        (config, errors)
+    }
+}
+
+/**
+   Resolve references in given value, which are ${PACKAGE_HOME}, ${HOME}, ${env:NAME} and ${package.name}.
+   ${env:NAME} becomes empty when NAME is not set like shells do.
+**/
+pub fn interpolate(
+    raw_value: &str,
+    package_name: &str,
+    package_home: Option<&String>,
+) -> Result<String, InterpolationError> {
+    let mut interpolated = String::new();
+    let mut rest = raw_value;
+
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find("}") {
+            None => {
+                return Err(InterpolationError { error_type: UnclosedReference(raw_value.to_string()) });
+            }
+            Some(value) => start + value,
+        };
+
+        interpolated.push_str(&rest[..start]);
+        interpolated.push_str(&resolve_reference(&rest[start + 2..end], package_name, package_home)?);
+        rest = &rest[end + 1..];
+    }
+
+    interpolated.push_str(rest);
+
+    Ok(interpolated)
+}
+
+fn resolve_reference(
+    name: &str,
+    package_name: &str,
+    package_home: Option<&String>,
+) -> Result<String, InterpolationError> {
+    match name {
+        "PACKAGE_HOME" => match package_home {
+            None => Err(InterpolationError { error_type: PackageHomeNotSet }),
+            Some(value) => Ok(value.to_string()),
+        },
+        // Runners run as the user who ran sudo for CLI, so CLI checks targets in home of that user as well.
+        "HOME" => match user_home_dir() {
+            Err(_) => Err(InterpolationError { error_type: HomeNotFound }),
+            Ok(value) => Ok(value.to_string_lossy().to_string()),
+        },
+        "package.name" => Ok(package_name.to_string()),
+        _ => match name.strip_prefix(ENV_REFERENCE_PREFIX) {
+            Some(value) if !value.is_empty() => Ok(env::var(value).unwrap_or_default()),
+            _ => Err(InterpolationError { error_type: UnknownReference(name.to_string()) }),
+        },
+    }
+}
+
+/**
+   Whether given raw value refers to ${env:NAME}.
+   It resolves to env of the runner only when it runs, so files it points to can't be checked beforehand.
+**/
+pub fn refers_to_env(raw_value: &str) -> bool {
+    references(raw_value).iter()
+        .any(| value | value.starts_with(ENV_REFERENCE_PREFIX))
+}
+
+fn references(raw_value: &str) -> Vec<&str> {
+    let mut names: Vec<&str> = vec![];
+    let mut rest = raw_value;
+
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find("}") {
+            None => break,
+            Some(value) => start + value,
+        };
+
+        names.push(&rest[start + 2..end]);
+        rest = &rest[end + 1..];
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common_models::models::configurations::env_var::EnvValue;
+    use crate::common_models::models::configurations::target_binder::TargetBinder;
+
+    const SET_ENV: &str = "PKG_SELECT_TEST_INTERPOLATION_SET";
+    const UNSET_ENV: &str = "PKG_SELECT_TEST_INTERPOLATION_UNSET";
+
+    fn binder(target_path: &str) -> TargetBinder {
+        TargetBinder {
+            target_name: "java".to_string(),
+            target_path: target_path.to_string(),
+            execution_path: "/usr/local/bin/java".to_string(),
+        }
+    }
+
+    #[test]
+    fn references_are_resolved() {
+        let home = "/opt/jdk".to_string();
+
+        assert_eq!(
+            interpolate("${PACKAGE_HOME}/bin/${package.name}", "temurin-21", Some(&home)).unwrap(),
+            "/opt/jdk/bin/temurin-21"
+        );
+        assert_eq!(interpolate("/usr/bin/java", "temurin-21", None).unwrap(), "/usr/bin/java");
+    }
+
+    #[test]
+    fn env_reference_resolves_to_empty_when_unset() {
+        env::set_var(SET_ENV, "/opt");
+        env::remove_var(UNSET_ENV);
+
+        assert_eq!(interpolate(&format!("${{env:{}}}/jdk", SET_ENV), "jdk", None).unwrap(), "/opt/jdk");
+        assert_eq!(interpolate(&format!("${{env:{}}}/jdk", UNSET_ENV), "jdk", None).unwrap(), "/jdk");
+    }
+
+    #[test]
+    fn invalid_references_are_errors() {
+        assert_eq!(
+            interpolate("${PACKAGE_HOME}/bin", "jdk", None).unwrap_err().error_type,
+            PackageHomeNotSet
+        );
+        assert_eq!(
+            interpolate("${JAVA_HOME}/bin", "jdk", None).unwrap_err().error_type,
+            UnknownReference("JAVA_HOME".to_string())
+        );
+        assert_eq!(
+            interpolate("${env:}/bin", "jdk", None).unwrap_err().error_type,
+            UnknownReference("env:".to_string())
+        );
+        assert_eq!(
+            interpolate("${PACKAGE_HOME/bin", "jdk", None).unwrap_err().error_type,
+            UnclosedReference("${PACKAGE_HOME/bin".to_string())
+        );
+    }
+
+    #[test]
+    fn package_is_interpolated_with_relative_target_resolved_against_home() {
+        let package = RunnablePackage {
+            name: "temurin-21".to_string(),
+            home: Some("/opt/${package.name}".to_string()),
+            envs: [("JAVA_HOME".to_string(), EnvValue::set("${PACKAGE_HOME}"))].into_iter().collect(),
+            binders: vec![binder("bin/java"), binder("/usr/bin/java")],
+            included_paths: vec!["${HOME}/projects".to_string()],
+            excluded_paths: vec!["${UNKNOWN}".to_string()],
+            ..Default::default()
+        };
+        let (interpolated, errors) = package.interpolated();
+
+        assert_eq!(interpolated.home, Some("/opt/temurin-21".to_string()));
+        assert_eq!(interpolated.envs["JAVA_HOME"].value, "/opt/temurin-21");
+        assert_eq!(interpolated.binders[0].target_path, "/opt/temurin-21/bin/java");
+        assert_eq!(interpolated.binders[1].target_path, "/usr/bin/java");
+        assert!(interpolated.included_paths[0].ends_with("/projects"));
+        assert!(!interpolated.included_paths[0].starts_with("${HOME}"));
+        assert_eq!(interpolated.excluded_paths[0], "${UNKNOWN}");
+        assert_eq!(
+            errors,
+            vec![(
+                "excluded_paths[0]".to_string(),
+                InterpolationError { error_type: UnknownReference("UNKNOWN".to_string()) },
+            )]
+        );
+    }
+
+    #[test]
+    fn undefined_env_references_are_listed_with_locations() {
+        env::set_var(SET_ENV, "/opt");
+        env::remove_var(UNSET_ENV);
+
+        let package = RunnablePackage {
+            name: "jdk".to_string(),
+            binders: vec![
+                binder(&format!("${{env:{}}}/java", SET_ENV)),
+                binder(&format!("${{env:{}}}/java", UNSET_ENV)),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.undefined_env_references(),
+            vec![("binders[1]".to_string(), UNSET_ENV.to_string())]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn home_reference_resolves_to_home_of_sudo_user() {
+        let sudo_user = env::var_os("SUDO_USER");
+        env::set_var("SUDO_USER", "pkg-select-test");
+
+        let interpolated = interpolate("${HOME}/.jdks", "jdk", None);
+
+        match sudo_user {
+            None => env::remove_var("SUDO_USER"),
+            Some(value) => env::set_var("SUDO_USER", value),
+        }
+        assert_eq!(interpolated.unwrap(), "/home/pkg-select-test/.jdks");
+    }
+
+    #[test]
+    fn only_env_references_are_resolved_by_runner() {
+        assert!(refers_to_env("${env:JAVA_HOME}/bin/java"));
+        assert!(refers_to_env("${PACKAGE_HOME}/${env:ARCH}"));
+        assert!(!refers_to_env("${PACKAGE_HOME}/bin/java"));
+        assert!(!refers_to_env("/usr/bin/java"));
+        assert!(!refers_to_env("${env:JAVA_HOME"));
+    }
+}
@@ -1,3 +1,7 @@
 pub mod configurations;
 pub mod errors;
+pub mod interpolation_impl;
+pub mod package_evaluation_impl;
+pub mod project_selection_impl;
+pub mod runner_index_impl;
 pub mod runtime_config_impl;
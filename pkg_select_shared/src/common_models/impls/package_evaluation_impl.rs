@@ -0,0 +1,44 @@
+use crate::common_models::models::package_evaluation::{SelectionReason, SkipReason};
+use std::fmt;
+use std::fmt::Formatter;
+
+impl fmt::Display for SelectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionReason::ProjectFile(value) => {
+                write!(f, "selected by project file {}", value)
+            }
+            SelectionReason::IncludedPath(value) => {
+                write!(f, "working path is in included path {}", value)
+            }
+            SelectionReason::DefaultPackage => {
+                write!(f, "default package of its category")
+            }
+            SelectionReason::FirstCandidate => {
+                write!(f, "earliest bound package, since no other rule matched")
+            }
+            SelectionReason::EnvOverride(value) => {
+                write!(f, "overridden by env {}", value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::ExcludedPath(value) => {
+                write!(f, "working path is in excluded path {}", value)
+            }
+            SkipReason::NotDefaultPackage => {
+                write!(f, "not default package and no included path matched")
+            }
+            SkipReason::OutPrioritized => {
+                write!(f, "another package was selected first")
+            }
+            SkipReason::Overridden(value) => {
+                write!(f, "another package is selected by env {}", value)
+            }
+        }
+    }
+}
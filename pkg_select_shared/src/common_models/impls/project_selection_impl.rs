@@ -0,0 +1,57 @@
+use crate::common_models::models::errors::project_selection::ProjectSelectionError;
+use crate::common_models::models::errors::project_selection::Type::{Malformed, Unreadable};
+use crate::common_models::models::project_selection::{ProjectSelection, PROJECT_SELECTION_FILE_NAME};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+impl ProjectSelection {
+    /**
+       Walk up from working path and return the nearest [ProjectSelection].
+       A file which couldn't be read or parsed is ignored, and the walk continues to its parents.
+       Ignored files are returned as well, so that they can be reported.
+    **/
+    pub fn find(working_path: &Path) -> (Option<ProjectSelection>, Vec<ProjectSelectionError>) {
+        let mut errors: Vec<ProjectSelectionError> = vec![];
+
+        for value in working_path.ancestors() {
+            let path = value.join(PROJECT_SELECTION_FILE_NAME);
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let raw_data = match fs::read_to_string(&path) {
+                Ok(value2) => value2,
+                Err(error) => {
+                    errors.push(ProjectSelectionError {
+                        error_type: Unreadable(path.to_string_lossy().to_string(), error.to_string())
+                    });
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<HashMap<String, String>>(&raw_data) {
+                Ok(value2) => {
+                    let selection = ProjectSelection {
+                        path,
+                        packages: value2,
+                    };
+
+                    return (Some(selection), errors);
+                }
+                Err(error) => {
+                    errors.push(ProjectSelectionError {
+                        error_type: Malformed(path.to_string_lossy().to_string(), error.to_string())
+                    });
+                }
+            }
+        }
+
+        (None, errors)
+    }
+
+    pub fn package_name(&self, category_name: &String) -> Option<&String> {
+        self.packages.get(category_name)
+    }
+}
@@ -0,0 +1,213 @@
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::common_models::impls::runtime_config_impl::runner_paths;
+use crate::common_models::models::configurations::package_category::PackageCategory;
+use crate::common_models::models::runner_index::{ConfigStamp, RunnerIndex};
+use crate::common_models::models::runtime_config::RuntimeConfig;
+use crate::common_models::traits::binder_converter::BinderConverter;
+
+impl RunnerIndex {
+    pub fn build(config: &RuntimeConfig, config_stamp: ConfigStamp, runner_path: Option<&PathBuf>) -> RunnerIndex {
+        let mut index = RunnerIndex {
+            package_category_hash: config.package_category_hash.to_string(),
+            config_stamp,
+            runner_paths: runner_paths(config, runner_path).iter()
+                .map(| value | value.to_string_lossy().to_string())
+                .collect(),
+            entries: Default::default(),
+        };
+
+        for value in config.package_categories.iter() {
+            for value2 in value.packages.iter() {
+                for value3 in value2.binders.iter() {
+                    let key = value3.convert_exec_to_pathbuf().to_string_lossy().to_string();
+                    let categories = index.entries.entry(key).or_insert(vec![]);
+
+                    let category = match categories.iter().position(| value4 | value4.name.eq(&value.name)) {
+                        Some(value4) => &mut categories[value4],
+                        None => {
+                            let mut category = value.clone();
+                            category.packages = vec![];
+                            categories.push(category);
+
+                            categories.last_mut().unwrap()
+                        }
+                    };
+
+                    if !category.packages.iter().any(| value4 | value4.name.eq(&value2.name)) {
+                        category.packages.push(value2.clone());
+                    }
+                }
+            }
+        }
+
+        index
+    }
+
+    /**
+       Config narrowed down to the categories bound to given process path, or nothing if it isn't indexed.
+    **/
+    pub fn config_for(&self, process_path: &Path) -> Option<RuntimeConfig> {
+        let categories: &Vec<PackageCategory> = self.entries.get(&process_path.to_string_lossy().to_string())?;
+
+        Some(RuntimeConfig {
+            package_category_hash: self.package_category_hash.to_string(),
+            package_categories: categories.clone(),
+            ..RuntimeConfig::default()
+        })
+    }
+
+    pub fn runner_paths(&self) -> Vec<PathBuf> {
+        self.runner_paths.iter()
+            .map(PathBuf::from)
+            .collect()
+    }
+}
+
+impl ConfigStamp {
+    /**
+       Stamp of given file, which tells whether an index is made from the current config.
+    **/
+    pub fn of(path: &Path) -> Option<ConfigStamp> {
+        let metadata = path.metadata().ok()?;
+        let modified = metadata.modified().ok()?
+            .duration_since(UNIX_EPOCH).ok()?
+            .as_nanos() as u64;
+        let (device, inode) = file_id(&metadata);
+
+        Some(ConfigStamp {
+            modified,
+            size: metadata.len(),
+            device,
+            inode,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+
+    (metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_: &Metadata) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common_models::models::configurations::runnable_package::RunnablePackage;
+    use crate::common_models::models::configurations::target_binder::TargetBinder;
+    use std::{fs, process};
+
+    fn stamp() -> ConfigStamp {
+        ConfigStamp {
+            modified: 1,
+            ..Default::default()
+        }
+    }
+
+    fn binder(target_name: &str, execution_path: &str) -> TargetBinder {
+        TargetBinder {
+            target_name: target_name.to_string(),
+            target_path: "/opt/jdk/bin".to_string(),
+            execution_path: execution_path.to_string(),
+        }
+    }
+
+    fn package(name: &str, binders: Vec<TargetBinder>) -> RunnablePackage {
+        RunnablePackage {
+            name: name.to_string(),
+            binders,
+            ..Default::default()
+        }
+    }
+
+    fn config(execution_path: &str) -> RuntimeConfig {
+        RuntimeConfig {
+            package_category_hash: "hash".to_string(),
+            package_categories: vec![PackageCategory {
+                name: "jdk".to_string(),
+                packages: vec![
+                    package("temurin-21", vec![binder("java", execution_path), binder("javac", execution_path)]),
+                    package("zulu-11", vec![binder("java", execution_path)]),
+                ],
+                default_package: Some("zulu-11".to_string()),
+                ..Default::default()
+            }],
+            ..RuntimeConfig::default()
+        }
+    }
+
+    #[test]
+    fn categories_are_narrowed_down_to_packages_bound_to_execution_path() {
+        let index = RunnerIndex::build(&config("/usr/local/bin"), stamp(), None);
+        let java = index.config_for(Path::new("/usr/local/bin/java")).unwrap();
+        let javac = index.config_for(Path::new("/usr/local/bin/javac")).unwrap();
+
+        assert_eq!(index.config_stamp, stamp());
+        assert_eq!(java.package_category_hash, "hash");
+        assert_eq!(java.package_categories.len(), 1);
+        assert_eq!(java.package_categories[0].default_package, Some("zulu-11".to_string()));
+        assert_eq!(
+            java.package_categories[0].packages.iter().map(| value | value.name.as_str()).collect::<Vec<&str>>(),
+            vec!["temurin-21", "zulu-11"]
+        );
+        assert_eq!(
+            javac.package_categories[0].packages.iter().map(| value | value.name.as_str()).collect::<Vec<&str>>(),
+            vec!["temurin-21"]
+        );
+    }
+
+    #[test]
+    fn path_which_is_not_indexed_has_no_config() {
+        let index = RunnerIndex::build(&config("/usr/local/bin"), stamp(), None);
+
+        assert!(index.config_for(Path::new("/usr/local/bin/jshell")).is_none());
+        assert!(index.config_for(Path::new("/usr/bin/java")).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runner_paths_are_canonical_paths_of_runners_bound_to_execution_path() {
+        let directory = std::env::temp_dir().join(format!("pkg-select-index-{}", process::id()));
+        fs::create_dir_all(directory.join("bin")).unwrap();
+        fs::write(directory.join("pkg_select_runner"), "").unwrap();
+        std::os::unix::fs::symlink(directory.join("pkg_select_runner"), directory.join("bin/java")).unwrap();
+
+        let runner_path = fs::canonicalize(directory.join("pkg_select_runner")).unwrap();
+        let index = RunnerIndex::build(&config(&directory.join("bin").to_string_lossy()), stamp(), Some(&runner_path));
+
+        assert_eq!(index.runner_paths(), vec![runner_path]);
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn missing_file_has_no_stamp() {
+        let path = std::env::temp_dir().join(format!("pkg-select-stamp-{}", process::id()));
+
+        assert_eq!(ConfigStamp::of(&path), None);
+        fs::write(&path, "").unwrap();
+        assert!(ConfigStamp::of(&path).is_some());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stamp_changes_when_file_is_replaced() {
+        let path = std::env::temp_dir().join(format!("pkg-select-stamp-replaced-{}", process::id()));
+        let replacing_path = path.with_extension("tmp");
+
+        fs::write(&path, "first").unwrap();
+        let stamp = ConfigStamp::of(&path).unwrap();
+        fs::write(&replacing_path, "other").unwrap();
+        fs::rename(&replacing_path, &path).unwrap();
+
+        assert_ne!(ConfigStamp::of(&path).unwrap(), stamp);
+        let _ = fs::remove_file(&path);
+    }
+}
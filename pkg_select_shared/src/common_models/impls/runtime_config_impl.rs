@@ -1,12 +1,16 @@
-use crate::common_models::models::errors::validation::Type::{EmptyCategoryList, NonUniqueName, NonUniqueTargetPath};
-use crate::common_models::models::errors::validation::ValidationError;
+use crate::common_models::models::errors::validation::Type::{EmptyCategoryList, NonUniqueName, NonUniqueOverrideEnvKey, NonUniqueTargetPath, ReservedOverrideEnvKey, TargetIsRunner, TargetNotFound};
+use crate::common_models::models::errors::validation::{Type, ValidationReport};
+use crate::common_models::impls::errors::validation_impl::child_location;
+use crate::common_models::impls::interpolation_impl::refers_to_env;
 use crate::common_models::models::errors::validation_combo::IOCanonicalSerdeValidationError;
 use crate::common_models::models::runtime_config::RuntimeConfig;
 use crate::common_models::traits::hashable_result::HashableResult;
 use crate::common_models::traits::validator::Validator;
-use crate::{safe_canonicalize, string_to_hash};
+use crate::{runner_file_path, safe_canonicalize, string_to_hash, RESERVED_ENV_KEYS};
+use std::path::{Path, PathBuf};
+#[cfg(not(target_os = "linux"))]
+use std::fs;
 use serde_json::Error;
-use crate::common_models::models::errors::canonical_path_combo::IOCanonicalError;
 use crate::common_models::traits::binder_converter::BinderConverter;
 
 impl Validator for RuntimeConfig {
@@ -14,8 +18,18 @@ impl Validator for RuntimeConfig {
         self.validatable.validated
     }
 
-    fn validate(&mut self) -> Result<(), IOCanonicalSerdeValidationError> {
-        validate_internal(self)
+    fn validate_into(&mut self, location: &str, report: &mut ValidationReport) -> Result<(), IOCanonicalSerdeValidationError> {
+        validate_internal(self, location, report)
+    }
+
+    fn warn_into(&self, location: &str, report: &mut ValidationReport) {
+        for value in self.package_categories.iter() {
+            value.warn_into(&child_location(location, &value.location()), report);
+        }
+    }
+
+    fn location(&self) -> String {
+        String::new()
     }
 
     fn invalidate(&mut self) {
@@ -23,93 +37,134 @@ impl Validator for RuntimeConfig {
     }
 }
 
-fn validate_internal(config: &mut RuntimeConfig) -> Result<(), IOCanonicalSerdeValidationError> {
+fn validate_internal(
+    config: &mut RuntimeConfig,
+    location: &str,
+    report: &mut ValidationReport,
+) -> Result<(), IOCanonicalSerdeValidationError> {
     if config.package_categories.is_empty() {
-        return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-            error_type: EmptyCategoryList,
-        }));
+        report.error(&child_location(location, "categories"), EmptyCategoryList);
+        return Ok(());
     }
 
-    let config_hash = match config.hash() {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::SerdeError(error));
-        }
-    };
+    // Targets can disappear while hash of config stays the same, so every finding is checked again for the report.
+    let error_count = report.errors().len();
 
-    if config.package_category_hash.eq(&config_hash) {
-        skip_validating_children(config);
-        return Ok(());
+    for value in config.package_categories.iter_mut() {
+        let category_location = child_location(location, &value.location());
+        value.validate_into(&category_location, report)?;
     }
 
-    for value in config.package_categories.iter_mut() {
-        match value.validate() {
-            Ok(_) => {},
-            Err(error) => return Err(error),
-        }
+    for (value, value2) in validate_name_uniqueness(config).iter() {
+        report.error(&child_location(location, value), NonUniqueName(value2.to_string()));
     }
 
-    match validate_name_uniqueness(config) {
-        None => {},
-        Some(value) => {
-            return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                error_type: NonUniqueName(value.to_string()),
-            }));
-        }
+    for (value, value2) in validate_override_env_keys(config).iter() {
+        report.error(&child_location(location, value), value2.clone());
     }
 
-    match validate_target_path_uniqueness(config) {
-        Ok(value) => {
-            match value {
-                None => {},
-                Some(value) => {
-                    return Err(IOCanonicalSerdeValidationError::ValidationError(ValidationError {
-                        error_type: NonUniqueTargetPath(value.to_string()),
-                    }));
-                }
-            }
-        }
-        Err(error) => {
-            return Err(IOCanonicalSerdeValidationError::IOCanonicalError(error));
-        }
+    // References are reported by packages, so only resolved targets are checked here.
+    let (interpolated, _) = config.interpolated();
+
+    for (value, value2) in validate_target_path_uniqueness(&interpolated, config).iter() {
+        report.error(&child_location(location, value), value2.clone());
     }
 
-    config.validatable.validated = true;
+    for (value, value2) in validate_runner_targets(&interpolated, runner_file_path().as_ref()).iter() {
+        report.error(&child_location(location, value), TargetIsRunner(value2.to_string()));
+    }
+
+    config.validatable.validated = report.errors().len() == error_count;
     Ok(())
 }
 
-pub fn validate_name_uniqueness<'t>(config: &'t RuntimeConfig) -> Option<&String> {
+/**
+   Returns location and name of every category or package whose name is used before.
+**/
+pub fn validate_name_uniqueness(config: &RuntimeConfig) -> Vec<(String, String)> {
     let mut names: Vec<&String> = vec![];
+    let mut duplicated: Vec<(String, String)> = vec![];
 
     for value in config.package_categories.iter() {
         if names.contains(&&value.name) {
-            return Some(&value.name);
+            duplicated.push((value.location(), value.name.to_string()));
         }
         names.push(&value.name);
 
         for value2 in value.packages.iter() {
             if names.contains(&&value2.name) {
-                return Some(&value2.name);
+                duplicated.push((
+                    child_location(&value.location(), &value2.location()),
+                    value2.name.to_string(),
+                ));
             }
             names.push(&value2.name);
         }
     }
 
-    None
+    duplicated
 }
 
-pub fn validate_target_path_uniqueness(config: &RuntimeConfig) -> Result<Option<String>, IOCanonicalError> {
-    let mut paths: Vec<String> = vec![];
+/**
+   Returns location and finding of every category whose override env var is reserved or is the one of a category before.
+   Categories of the same name are reported by [validate_name_uniqueness] instead.
+**/
+pub fn validate_override_env_keys(config: &RuntimeConfig) -> Vec<(String, Type)> {
+    let mut keys: Vec<(String, &String)> = vec![];
+    let mut findings: Vec<(String, Type)> = vec![];
 
     for value in config.package_categories.iter() {
-        for value2 in value.packages.iter() {
-            for value3 in value2.binders.iter() {
-                let current_path = safe_canonicalize(&value3.convert_target_to_pathbuf())?
-                    .to_string_lossy()
-                    .to_string();
+        let key = value.override_env_key();
+
+        if RESERVED_ENV_KEYS.contains(&key.as_str()) {
+            findings.push((value.location(), ReservedOverrideEnvKey(key)));
+            continue;
+        }
+
+        match keys.iter().find(| (value2, value3) | value2.eq(&key) && !value3.eq(&&value.name)) {
+            Some((_, value2)) => findings.push((value.location(), NonUniqueOverrideEnvKey(key.to_string(), value2.to_string()))),
+            None => keys.push((key, &value.name)),
+        }
+    }
+
+    findings
+}
+
+/**
+   Returns location and finding of every binder whose target doesn't exist or is bound before.
+   Targets referring to env in raw config are resolved by the runner, so they aren't reported missing.
+**/
+pub fn validate_target_path_uniqueness(config: &RuntimeConfig, raw_config: &RuntimeConfig) -> Vec<(String, Type)> {
+    let mut paths: Vec<String> = vec![];
+    let mut findings: Vec<(String, Type)> = vec![];
+
+    for (category_index, value) in config.package_categories.iter().enumerate() {
+        for (package_index, value2) in value.packages.iter().enumerate() {
+            for (index, value3) in value2.binders.iter().enumerate() {
+                let binder_location = child_location(
+                    &child_location(&value.location(), &value2.location()),
+                    &format!("binders[{}]", index),
+                );
+                let target_path = value3.convert_target_to_pathbuf();
+                let current_path = match safe_canonicalize(&target_path) {
+                    Ok(value4) => value4.to_string_lossy().to_string(),
+                    Err(_) => {
+                        let runtime_target = raw_config.package_categories.get(category_index)
+                            .and_then(| value4 | value4.packages.get(package_index))
+                            .and_then(| value4 | value4.binders.get(index))
+                            .map(| value4 | refers_to_env(&value4.target_path))
+                            .unwrap_or(false);
+
+                        if !runtime_target {
+                            findings.push((binder_location, TargetNotFound(target_path.to_string_lossy().to_string())));
+                        }
+                        continue;
+                    }
+                };
 
                 if paths.contains(&current_path) {
-                    return Ok(Some(current_path));
+                    findings.push((binder_location, NonUniqueTargetPath(current_path)));
+                    continue;
                 }
 
                 paths.push(current_path);
@@ -117,17 +172,96 @@ pub fn validate_target_path_uniqueness(config: &RuntimeConfig) -> Result<Option<
         }
     }
 
-    Ok(None)
+    findings
 }
 
-fn skip_validating_children(package: &mut RuntimeConfig) {
-    for value in package.package_categories.iter_mut() {
-        value.validatable.validated = true;
+/**
+   Returns location and target of every binder whose target resolves to the runner or to a runner registered for binders.
+**/
+pub fn validate_runner_targets(config: &RuntimeConfig, runner_path: Option<&PathBuf>) -> Vec<(String, String)> {
+    let runner_paths = runner_paths(config, runner_path);
+    let mut findings: Vec<(String, String)> = vec![];
+
+    for value in config.package_categories.iter() {
+        for value2 in value.packages.iter() {
+            for (index, value3) in value2.binders.iter().enumerate() {
+                let target_path = value3.convert_target_to_pathbuf();
 
-        for value2 in value.packages.iter_mut() {
-            value2.validatable.validated = true;
+                if resolves_to_runner(&target_path, &runner_paths) {
+                    findings.push((
+                        child_location(
+                            &child_location(&value.location(), &value2.location()),
+                            &format!("binders[{}]", index),
+                        ),
+                        target_path.to_string_lossy().to_string(),
+                    ));
+                }
+            }
         }
     }
+
+    findings
+}
+
+/**
+   Runner itself and every execution path of binders registered as runner.
+   An execution path which resolves to anything else, such as the actual executable before it is synced, is not a runner.
+**/
+pub fn runner_paths(config: &RuntimeConfig, runner_path: Option<&PathBuf>) -> Vec<PathBuf> {
+    let runner_path = match runner_path {
+        None => return vec![],
+        Some(value) => value,
+    };
+    let mut paths: Vec<PathBuf> = vec![runner_path.clone()];
+
+    for value in config.package_categories.iter() {
+        for value2 in value.packages.iter() {
+            for value3 in value2.binders.iter() {
+                if let Ok(value4) = safe_canonicalize(&value3.convert_exec_to_pathbuf()) {
+                    if !paths.contains(&value4) && is_runner_copy(&value4, runner_path) {
+                        paths.push(value4);
+                    }
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/**
+   Runners are linked on Linux, so their canonical path is the runner itself and never a copy.
+**/
+#[cfg(target_os = "linux")]
+fn is_runner_copy(_: &Path, _: &Path) -> bool {
+    false
+}
+
+/**
+   Runners are copied on platforms other than Linux, so only a file identical to the runner is one.
+**/
+#[cfg(not(target_os = "linux"))]
+fn is_runner_copy(path: &Path, runner_path: &Path) -> bool {
+    let same_size = match (fs::metadata(path), fs::metadata(runner_path)) {
+        (Ok(value), Ok(value2)) => value.is_file() && value.len() == value2.len(),
+        _ => false,
+    };
+
+    if !same_size {
+        return false;
+    }
+
+    match (fs::read(path), fs::read(runner_path)) {
+        (Ok(value), Ok(value2)) => value.eq(&value2),
+        _ => false,
+    }
+}
+
+pub fn resolves_to_runner(target_path: &Path, runner_paths: &[PathBuf]) -> bool {
+    match safe_canonicalize(target_path) {
+        Ok(value) => runner_paths.contains(&value),
+        Err(_) => false,
+    }
 }
 
 fn invalidate(config: &mut RuntimeConfig) {
@@ -150,3 +284,120 @@ impl HashableResult<Error> for RuntimeConfig {
         Ok(string_to_hash(&config_json))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common_models::models::configurations::package_category::PackageCategory;
+    use crate::common_models::models::configurations::runnable_package::RunnablePackage;
+    use crate::common_models::models::configurations::target_binder::TargetBinder;
+    use std::{fs, process};
+
+    fn config(target_path: &Path, execution_path: &Path) -> RuntimeConfig {
+        RuntimeConfig {
+            package_categories: vec![PackageCategory {
+                name: "jdk".to_string(),
+                packages: vec![RunnablePackage {
+                    name: "temurin-21".to_string(),
+                    binders: vec![TargetBinder {
+                        target_name: "java".to_string(),
+                        target_path: target_path.to_string_lossy().to_string(),
+                        execution_path: execution_path.to_string_lossy().to_string(),
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..RuntimeConfig::default()
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("pkg-select-{}-{}", name, process::id()));
+        fs::create_dir_all(directory.join("jdk/bin")).unwrap();
+        fs::create_dir_all(directory.join("bin")).unwrap();
+        fs::write(directory.join("jdk/bin/java"), "java").unwrap();
+        fs::write(directory.join("pkg_select_runner"), "runner").unwrap();
+
+        directory
+    }
+
+    fn category(name: &str) -> PackageCategory {
+        PackageCategory {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn override_env_keys_must_be_unique_and_not_reserved() {
+        let config = RuntimeConfig {
+            package_categories: vec![category("jdk-8"), category("jdk_8"), category("runner.depth"), category("jdk")],
+            ..RuntimeConfig::default()
+        };
+
+        assert_eq!(
+            validate_override_env_keys(&config),
+            vec![
+                (
+                    category("jdk_8").location(),
+                    NonUniqueOverrideEnvKey("PKG_SELECT_JDK_8".to_string(), "jdk-8".to_string()),
+                ),
+                (
+                    category("runner.depth").location(),
+                    ReservedOverrideEnvKey("PKG_SELECT_RUNNER_DEPTH".to_string()),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn categories_of_same_name_are_not_reported_as_override_collision() {
+        let config = RuntimeConfig {
+            package_categories: vec![category("jdk"), category("jdk")],
+            ..RuntimeConfig::default()
+        };
+
+        assert!(validate_override_env_keys(&config).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn execution_path_linked_to_actual_target_is_not_runner() {
+        let directory = temp_dir("unsynced");
+        std::os::unix::fs::symlink(directory.join("jdk/bin/java"), directory.join("bin/java")).unwrap();
+
+        let runner_path = fs::canonicalize(directory.join("pkg_select_runner")).unwrap();
+        let config = config(&directory.join("jdk/bin"), &directory.join("bin"));
+
+        assert_eq!(runner_paths(&config, Some(&runner_path)), vec![runner_path.clone()]);
+        assert!(validate_runner_targets(&config, Some(&runner_path)).is_empty());
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn target_linked_to_runner_is_reported() {
+        let directory = temp_dir("looped");
+        std::os::unix::fs::symlink(directory.join("pkg_select_runner"), directory.join("bin/java")).unwrap();
+
+        let runner_path = fs::canonicalize(directory.join("pkg_select_runner")).unwrap();
+        let config = config(&directory.join("bin"), &directory.join("bin"));
+        let findings = validate_runner_targets(&config, Some(&runner_path));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].1, directory.join("bin/java").to_string_lossy());
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn nothing_is_runner_without_runner() {
+        let directory = temp_dir("no-runner");
+        fs::write(directory.join("bin/java"), "runner").unwrap();
+
+        let config = config(&directory.join("jdk/bin"), &directory.join("bin"));
+
+        assert!(runner_paths(&config, None).is_empty());
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
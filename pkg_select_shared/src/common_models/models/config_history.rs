@@ -0,0 +1,14 @@
+use std::path::PathBuf;
+
+/**
+   Snapshot of config which was overwritten by [ConfigPersistence::update].
+
+   [ConfigPersistence::update]: crate::config_resolver::traits::config_persistence::ConfigPersistence::update
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigHistoryEntry {
+    pub id: u64,
+    pub timestamp: u64,
+    pub package_category_hash: String,
+    pub path: PathBuf,
+}
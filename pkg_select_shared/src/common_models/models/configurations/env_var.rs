@@ -5,3 +5,27 @@ pub struct EnvVar {
     pub key: String,
     pub value: String,
 }
+
+/**
+   How [EnvValue] is applied to the env var inherited by runners.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnvOperation {
+    Set,
+    Prepend,
+    Append,
+    SetIfUnset,
+    Unset,
+}
+
+/**
+   Env var injected into a package. separator is used by prepend and append only,
+   and the path list separator of the platform is used when it is not set.
+**/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvValue {
+    pub operation: EnvOperation,
+    pub value: String,
+    pub separator: Option<String>,
+}
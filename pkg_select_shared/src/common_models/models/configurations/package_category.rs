@@ -6,9 +6,12 @@ use serde::{Deserialize, Serialize};
 pub struct PackageCategory {
     pub name: String,
     pub packages: Vec<RunnablePackage>,
-    pub default_package: Option<usize>,
+    /**
+       Name of the default package, so that it doesn't shift when packages are removed or reordered.
+    **/
+    pub default_package: Option<String>,
     #[serde(skip)]
-    pub(in crate::common_models) validatable: Validatable,
+    pub validatable: Validatable,
 }
 
 impl Default for PackageCategory {
@@ -16,7 +19,7 @@ impl Default for PackageCategory {
         PackageCategory {
             name: "".to_string(),
             packages: vec![],
-            default_package: Some(0),
+            default_package: None,
             validatable: Default::default(),
         }
     }
@@ -1,26 +1,29 @@
 use std::collections::HashMap;
+use crate::common_models::models::configurations::env_var::EnvValue;
 use crate::common_models::models::configurations::target_binder::TargetBinder;
 use crate::common_models::models::validatable::Validatable;
 use serde::{Deserialize, Serialize};
-use serde_with::{DisplayFromStr, serde_as};
-
-#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunnablePackage {
     pub name: String,
-    #[serde_as(as = "HashMap<DisplayFromStr, DisplayFromStr>")]
-    pub envs: HashMap<String, String>,
+    /**
+       Root of the package such as the install prefix of a JDK.
+       Relative target paths of binders resolve against it, and it is referred as ${PACKAGE_HOME}.
+    **/
+    pub home: Option<String>,
+    pub envs: HashMap<String, EnvValue>,
     pub binders: Vec<TargetBinder>,
     pub included_paths: Vec<String>,
     pub excluded_paths: Vec<String>,
     #[serde(skip)]
-    pub(in crate::common_models) validatable: Validatable,
+    pub validatable: Validatable,
 }
 
 impl Default for RunnablePackage {
     fn default() -> Self {
         RunnablePackage {
             name: "".to_string(),
+            home: None,
             envs: HashMap::default(),
             binders: vec![],
             included_paths: vec![],
@@ -1,4 +1,5 @@
 use crate::common_models::models::errors::directory_resolve::DirectoryResolveError;
+use crate::common_models::models::errors::schema_migration::SchemaMigrationError;
 use std::io;
 
 #[derive(Debug)]
@@ -6,4 +7,5 @@ pub enum SerdeIODirectoryError {
     SerdeError(serde_json::Error),
     IOError(io::Error),
     DirectoryResolveError(DirectoryResolveError),
+    SchemaMigrationError(SchemaMigrationError),
 }
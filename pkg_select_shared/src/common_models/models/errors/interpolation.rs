@@ -0,0 +1,12 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    UnknownReference(String),
+    UnclosedReference(String),
+    PackageHomeNotSet,
+    HomeNotFound,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterpolationError {
+    pub error_type: Type,
+}
@@ -2,5 +2,9 @@ pub mod canonical_path;
 pub mod canonical_path_combo;
 pub mod config_resolve_combo;
 pub mod directory_resolve;
+pub mod interpolation;
+pub mod package_override;
+pub mod project_selection;
+pub mod schema_migration;
 pub mod validation;
 pub mod validation_combo;
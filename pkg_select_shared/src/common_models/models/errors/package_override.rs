@@ -0,0 +1,13 @@
+#[derive(Debug)]
+pub enum Type {
+    BinderNotFound(String),
+    PackageNotFound(String),
+    PackageNotInCategory(String, String),
+    BinderNotInPackage(String, String),
+    InvalidEnvOverride(String, Box<Type>),
+}
+
+#[derive(Debug)]
+pub struct PackageOverrideError {
+    pub error_type: Type,
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Unreadable(String, String),
+    Malformed(String, String),
+    UnknownPackage(String, String, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSelectionError {
+    pub error_type: Type,
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, PartialEq)]
+pub enum Type {
+    InvalidSchemaVersion,
+    NewerSchemaVersion(u32),
+    MigrationFailed(u32),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SchemaMigrationError {
+    pub error_type: Type,
+}
@@ -1,21 +1,57 @@
-#[derive(Debug)]
+use crate::common_models::models::errors::interpolation::InterpolationError;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     InvalidBinderPath,
-    InvalidIncludedPath,
-    InvalidExcludedPath,
+    InvalidIncludedPath(String),
+    InvalidExcludedPath(String),
     InvalidDefaultPackage,
+    DefaultPackageNotFound(String),
     EmptyCategoryList,
     EmptyPackageList,
     EmptyBinderList,
-    DuplicatedBinderExecutionPath,
-    DuplicatedIncludedPath,
-    DuplicatedExcludedPath,
-    DuplicatedPathInIncludedAndExcluded,
+    DuplicatedBinderExecutionPath(String),
+    DuplicatedIncludedPath(String),
+    DuplicatedExcludedPath(String),
+    DuplicatedPathInIncludedAndExcluded(String),
     NonUniqueName(String),
+    NonUniqueOverrideEnvKey(String, String),
+    ReservedOverrideEnvKey(String),
     NonUniqueTargetPath(String),
+    TargetNotFound(String),
+    TargetNotExecutable(String),
+    TargetIsRunner(String),
+    ExecutionPathIsTargetPath(String),
+    IncludedPathInSiblingPackage(String, String),
+    EnvPathNotFound(String, String),
+    InvalidReference(InterpolationError),
+    UndefinedEnvReference(String),
+}
+
+/**
+   Errors block saving config, while warnings only block it with --strict.
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
 }
 
-#[derive(Debug)]
+/**
+   A single finding of validation.
+   Location looks like categories[jdk].packages[temurin-17].binders[2].
+**/
+#[derive(Debug, Clone)]
 pub struct ValidationError {
     pub error_type: Type,
+    pub severity: Severity,
+    pub location: String,
+}
+
+/**
+   Every finding of validation, so all of them can be fixed at once.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub findings: Vec<ValidationError>,
 }
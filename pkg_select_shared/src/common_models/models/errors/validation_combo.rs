@@ -1,9 +1,9 @@
 use crate::common_models::models::errors::canonical_path_combo::IOCanonicalError;
-use crate::common_models::models::errors::validation::ValidationError;
+use crate::common_models::models::errors::validation::ValidationReport;
 
 #[derive(Debug)]
 pub enum IOCanonicalSerdeValidationError {
     SerdeError(serde_json::Error),
-    ValidationError(ValidationError),
+    ValidationReport(ValidationReport),
     IOCanonicalError(IOCanonicalError),
 }
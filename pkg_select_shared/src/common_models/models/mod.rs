@@ -1,4 +1,8 @@
+pub mod config_history;
 pub mod configurations;
 pub mod errors;
+pub mod package_evaluation;
+pub mod project_selection;
+pub mod runner_index;
 pub mod runtime_config;
 pub mod validatable;
@@ -0,0 +1,37 @@
+use crate::common_models::models::configurations::package_category::PackageCategory;
+use crate::common_models::models::configurations::runnable_package::RunnablePackage;
+use crate::common_models::models::configurations::target_binder::TargetBinder;
+use crate::common_models::models::errors::project_selection::ProjectSelectionError;
+use crate::common_models::models::project_selection::ProjectSelection;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectionReason {
+    ProjectFile(String),
+    IncludedPath(String),
+    DefaultPackage,
+    FirstCandidate,
+    EnvOverride(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    ExcludedPath(String),
+    NotDefaultPackage,
+    OutPrioritized,
+    Overridden(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CandidatePackage<'t> {
+    pub category: &'t PackageCategory,
+    pub package: &'t RunnablePackage,
+    pub binder: &'t TargetBinder,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PackageEvaluation<'t> {
+    pub selected: Option<(CandidatePackage<'t>, SelectionReason)>,
+    pub skipped: Vec<(CandidatePackage<'t>, SkipReason)>,
+    pub project_selection: Option<ProjectSelection>,
+    pub project_selection_errors: Vec<ProjectSelectionError>,
+}
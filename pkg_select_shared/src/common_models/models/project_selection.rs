@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub const PROJECT_SELECTION_FILE_NAME: &str = ".pkg-select.json";
+
+/**
+   Project-local selection file which names [RunnablePackage] per [PackageCategory], such as `{"jdk": "temurin-17"}`.
+   It is discovered by walking up from working path, and takes precedence over included_paths in config.
+
+   [RunnablePackage]: crate::common_models::models::configurations::runnable_package::RunnablePackage
+   [PackageCategory]: crate::common_models::models::configurations::package_category::PackageCategory
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSelection {
+    pub path: PathBuf,
+    pub packages: HashMap<String, String>,
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+use crate::common_models::models::configurations::package_category::PackageCategory;
+use serde::{Deserialize, Serialize};
+
+/**
+   Precomputed lookup for runners, so that they don't parse and scan the whole config on every execution.
+   It is valid only while config is not modified after it is written, which is told by [ConfigStamp] of config.
+**/
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunnerIndex {
+    pub package_category_hash: String,
+    pub config_stamp: ConfigStamp,
+    /**
+       Canonical paths of the runner and of runners registered for binders, to tell whether a target resolves to a runner.
+    **/
+    pub runner_paths: Vec<String>,
+    /**
+       Categories keyed by execution path of binders, with the packages having a binder there only.
+    **/
+    pub entries: HashMap<String, Vec<PackageCategory>>,
+}
+
+/**
+   Metadata of config read by stat alone, so that runners tell a stale index without reading config.
+   Config is always replaced by rename, so its inode changes on every write even when modified time stays the same.
+**/
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigStamp {
+    pub modified: u64,
+    pub size: u64,
+    pub device: u64,
+    pub inode: u64,
+}
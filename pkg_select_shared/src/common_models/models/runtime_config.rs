@@ -2,17 +2,24 @@ use crate::common_models::models::configurations::package_category::PackageCateg
 use crate::common_models::models::validatable::Validatable;
 use serde::{Deserialize, Serialize};
 
+/**
+   Bump this with a new migration in [crate::config_resolver::config_migration] whenever the shape of config changes.
+**/
+pub const CURRENT_SCHEMA_VERSION: u32 = 4;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuntimeConfig {
+    pub schema_version: u32,
     pub package_category_hash: String,
     pub package_categories: Vec<PackageCategory>,
     #[serde(skip)]
-    pub(crate) validatable: Validatable,
+    pub validatable: Validatable,
 }
 
 impl Default for RuntimeConfig {
     fn default() -> Self {
         RuntimeConfig {
+            schema_version: CURRENT_SCHEMA_VERSION,
             package_category_hash: "".to_string(),
             package_categories: vec![],
             validatable: Default::default(),
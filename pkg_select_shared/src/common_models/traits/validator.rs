@@ -1,7 +1,30 @@
+use crate::common_models::models::errors::validation::ValidationReport;
 use crate::common_models::models::errors::validation_combo::IOCanonicalSerdeValidationError;
 
 pub trait Validator {
     fn validated(&self) -> bool;
-    fn validate(&mut self) -> Result<(), IOCanonicalSerdeValidationError>;
+    /**
+       Returns report including warnings only when validated.
+       Otherwise returns IOCanonicalSerdeValidationError::ValidationReport with every finding.
+    **/
+    fn validate(&mut self) -> Result<ValidationReport, IOCanonicalSerdeValidationError> {
+        let mut report = ValidationReport::default();
+        let location = self.location();
+
+        self.validate_into(&location, &mut report)?;
+        self.warn_into(&location, &mut report);
+
+        if report.has_errors() {
+            return Err(IOCanonicalSerdeValidationError::ValidationReport(report));
+        }
+
+        Ok(report)
+    }
+    fn validate_into(&mut self, location: &str, report: &mut ValidationReport) -> Result<(), IOCanonicalSerdeValidationError>;
+    /**
+       Warnings depend on files outside of config, so they are collected every time.
+    **/
+    fn warn_into(&self, location: &str, report: &mut ValidationReport);
+    fn location(&self) -> String;
     fn invalidate(&mut self);
 }
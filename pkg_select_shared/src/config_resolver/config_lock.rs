@@ -0,0 +1,184 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, io, process, thread};
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/**
+   Advisory lock to serialize writers of config, held by the OS on the lock file while [ConfigLock] lives.
+   The OS releases it when the holder exits or crashes, so a lock is never left stale.
+   The lock file itself is kept, since removing it would let another writer lock a new file while one still locks the old one.
+   Readers don't need the lock because config is always replaced by rename.
+**/
+pub struct ConfigLock {
+    file: File,
+}
+
+impl ConfigLock {
+    pub fn acquire(path: PathBuf) -> io::Result<ConfigLock> {
+        let started = SystemTime::now();
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
+
+        loop {
+            match file.try_lock() {
+                Ok(_) => {
+                    // Holder is written only to tell users which process holds the lock.
+                    let _ = file.set_len(0).and_then(| _ | write!(file, "{}", process::id()));
+
+                    return Ok(ConfigLock { file });
+                }
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(error)) => return Err(error),
+            }
+
+            let elapsed = started.elapsed().unwrap_or(Duration::ZERO);
+            if elapsed > LOCK_TIMEOUT {
+                let holder = fs::read_to_string(&path).unwrap_or_default();
+
+                return Err(io::Error::new(
+                    ErrorKind::TimedOut,
+                    format!(
+                        "config is locked by another process {}. Run the command again after it finishes.",
+                        holder.trim()
+                    ),
+                ));
+            }
+
+            thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/**
+   Write data into temp file next to given path, flush it to disk and rename it over the path.
+   Readers see either the previous content or the new one, never a truncated file.
+   Permissions and owner of the existing file are kept, since the temp file is created with the umask of current user.
+**/
+pub fn write_atomically(path: &PathBuf, data: &str) -> io::Result<()> {
+    let file_name = match path.file_name() {
+        None => {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "path has no file name"));
+        }
+        Some(value) => value.to_string_lossy().to_string(),
+    };
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+
+    let result = write_and_sync(&temp_path, data)
+        .and_then(| _ | copy_file_attributes(path, &temp_path))
+        .and_then(| _ | fs::rename(&temp_path, path));
+
+    match result {
+        Ok(_) => {}
+        Err(error) => {
+            let _ = fs::remove_file(&temp_path);
+            return Err(error);
+        }
+    }
+
+    sync_parent_dir(path);
+
+    Ok(())
+}
+
+fn write_and_sync(path: &PathBuf, data: &str) -> io::Result<()> {
+    let mut file = File::create(path)?;
+
+    file.write_all(data.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn copy_file_attributes(from: &PathBuf, to: &PathBuf) -> io::Result<()> {
+    use std::os::unix::fs::{chown, MetadataExt};
+
+    let metadata = match fs::metadata(from) {
+        Ok(value) => value,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    fs::set_permissions(to, metadata.permissions())?;
+    chown(to, Some(metadata.uid()), Some(metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn copy_file_attributes(from: &PathBuf, to: &PathBuf) -> io::Result<()> {
+    match fs::metadata(from) {
+        Ok(value) => fs::set_permissions(to, value.permissions()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    match path.parent() {
+        None => {}
+        Some(value) => {
+            let _ = File::open(value).and_then(| value2 | value2.sync_all());
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pkg-select-{}-{}", name, process::id()))
+    }
+
+    #[test]
+    fn written_file_is_replaced_at_once() {
+        let path = temp_path("atomic");
+
+        write_atomically(&path, "first").unwrap();
+        write_atomically(&path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn permissions_of_existing_file_are_kept() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path("permissions");
+
+        fs::write(&path, "first").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        write_atomically(&path, "second").unwrap();
+
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn lock_is_held_until_dropped() {
+        let path = temp_path("lock");
+
+        let lock = ConfigLock::acquire(path.clone()).unwrap();
+        let other = File::open(&path).unwrap();
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), process::id().to_string());
+
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+        drop(other);
+
+        let _ = fs::remove_file(&path);
+    }
+}
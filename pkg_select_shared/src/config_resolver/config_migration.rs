@@ -0,0 +1,353 @@
+use crate::common_models::models::errors::schema_migration::SchemaMigrationError;
+use crate::common_models::models::errors::schema_migration::Type::{InvalidSchemaVersion, MigrationFailed, NewerSchemaVersion};
+use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
+use crate::common_models::models::runtime_config::{RuntimeConfig, CURRENT_SCHEMA_VERSION};
+use serde_json::{Map, Value};
+
+type Migration = fn(&mut Map<String, Value>) -> bool;
+
+/**
+   Migrations ordered by schema version they upgrade from.
+   Migration at index N upgrades a document from version N to N + 1,
+   so its length must be the same as [CURRENT_SCHEMA_VERSION].
+**/
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [
+    migrate_from_0,
+    migrate_from_1,
+    migrate_from_2,
+    migrate_from_3,
+];
+
+/**
+   Documents written before schema_version was introduced are considered as version 0.
+**/
+pub fn schema_version(document: &Value) -> Result<u32, SchemaMigrationError> {
+    match document.get("schema_version") {
+        None => Ok(0),
+        Some(value) => match value.as_u64() {
+            None => Err(SchemaMigrationError { error_type: InvalidSchemaVersion }),
+            Some(value2) => match u32::try_from(value2) {
+                Ok(value3) => Ok(value3),
+                Err(_) => Err(SchemaMigrationError { error_type: InvalidSchemaVersion }),
+            },
+        },
+    }
+}
+
+/**
+   Upgrade given document step by step until it reaches [CURRENT_SCHEMA_VERSION].
+   Returns the version of document before migration.
+**/
+pub fn migrate(document: &mut Value) -> Result<u32, SchemaMigrationError> {
+    let version = schema_version(document)?;
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(SchemaMigrationError { error_type: NewerSchemaVersion(version) });
+    }
+
+    let object = match document.as_object_mut() {
+        None => {
+            return Err(SchemaMigrationError { error_type: MigrationFailed(version) });
+        }
+        Some(value) => value,
+    };
+
+    for value in version..CURRENT_SCHEMA_VERSION {
+        if !MIGRATIONS[value as usize](object) {
+            return Err(SchemaMigrationError { error_type: MigrationFailed(value) });
+        }
+
+        object.insert("schema_version".to_string(), Value::from(value + 1));
+    }
+
+    Ok(version)
+}
+
+/**
+   Parse raw config and migrate it to [CURRENT_SCHEMA_VERSION].
+   Returns the version of document before migration as well.
+**/
+pub fn deserialize_config(raw_data: &str) -> Result<(RuntimeConfig, u32), SerdeIODirectoryError> {
+    let mut document: Value = match serde_json::from_str(raw_data) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(SerdeIODirectoryError::SerdeError(error));
+        }
+    };
+    let version = match migrate(&mut document) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(SerdeIODirectoryError::SchemaMigrationError(error));
+        }
+    };
+
+    match serde_json::from_value(document) {
+        Ok(value) => Ok((value, version)),
+        Err(error) => Err(SerdeIODirectoryError::SerdeError(error)),
+    }
+}
+
+/**
+   Version 0 has the same shape as version 1 except for schema_version itself.
+**/
+fn migrate_from_0(_document: &mut Map<String, Value>) -> bool {
+    true
+}
+
+/**
+   Version 1 stores default_package as an index of packages, and version 2 stores its name instead.
+   An index out of range becomes null, which is reported by validation.
+**/
+fn migrate_from_1(document: &mut Map<String, Value>) -> bool {
+    let categories = match document.get_mut("package_categories") {
+        None => {
+            return true;
+        }
+        Some(value) => match value.as_array_mut() {
+            None => {
+                return false;
+            }
+            Some(value2) => value2,
+        },
+    };
+
+    for value in categories.iter_mut() {
+        let category = match value.as_object_mut() {
+            None => {
+                return false;
+            }
+            Some(value2) => value2,
+        };
+
+        let default_name = category.get("default_package")
+            .and_then(| value2 | value2.as_u64())
+            .and_then(| value2 | {
+                category.get("packages")
+                    .and_then(| value3 | value3.get(value2 as usize))
+                    .and_then(| value3 | value3.get("name"))
+                    .cloned()
+            })
+            .unwrap_or(Value::Null);
+
+        category.insert("default_package".to_string(), default_name);
+    }
+
+    true
+}
+
+/**
+   Version 2 stores envs as plain values which overwrite the inherited ones,
+   and version 3 stores an operation with each value, so they become set operations.
+**/
+fn migrate_from_2(document: &mut Map<String, Value>) -> bool {
+    let categories = match document.get_mut("package_categories") {
+        None => {
+            return true;
+        }
+        Some(value) => match value.as_array_mut() {
+            None => {
+                return false;
+            }
+            Some(value2) => value2,
+        },
+    };
+
+    for value in categories.iter_mut() {
+        let packages = match value.get_mut("packages").and_then(| value2 | value2.as_array_mut()) {
+            None => {
+                continue;
+            }
+            Some(value2) => value2,
+        };
+
+        for value2 in packages.iter_mut() {
+            let envs = match value2.get_mut("envs").and_then(| value3 | value3.as_object_mut()) {
+                None => {
+                    continue;
+                }
+                Some(value3) => value3,
+            };
+
+            for (_, value3) in envs.iter_mut() {
+                let raw_value = match value3.as_str() {
+                    None => {
+                        return false;
+                    }
+                    Some(value4) => value4.to_string(),
+                };
+
+                *value3 = serde_json::json!({
+                    "operation": "set",
+                    "value": raw_value,
+                    "separator": null,
+                });
+            }
+        }
+    }
+
+    true
+}
+
+/**
+   Version 4 adds home to packages, which is not set for existing ones.
+**/
+fn migrate_from_3(document: &mut Map<String, Value>) -> bool {
+    let categories = match document.get_mut("package_categories") {
+        None => {
+            return true;
+        }
+        Some(value) => match value.as_array_mut() {
+            None => {
+                return false;
+            }
+            Some(value2) => value2,
+        },
+    };
+
+    for value in categories.iter_mut() {
+        let packages = match value.get_mut("packages").and_then(| value2 | value2.as_array_mut()) {
+            None => {
+                continue;
+            }
+            Some(value2) => value2,
+        };
+
+        for value2 in packages.iter_mut() {
+            match value2.as_object_mut() {
+                None => {
+                    return false;
+                }
+                Some(value3) => {
+                    value3.entry("home").or_insert(Value::Null);
+                }
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn missing_schema_version_is_version_0() {
+        assert_eq!(schema_version(&json!({})).unwrap(), 0);
+        assert_eq!(schema_version(&json!({ "schema_version": 2 })).unwrap(), 2);
+    }
+
+    #[test]
+    fn schema_version_out_of_u32_is_rejected() {
+        let document = json!({ "schema_version": u64::from(u32::MAX) + 1 });
+
+        assert_eq!(schema_version(&document).unwrap_err().error_type, InvalidSchemaVersion);
+        assert_eq!(schema_version(&json!({ "schema_version": "1" })).unwrap_err().error_type, InvalidSchemaVersion);
+        assert_eq!(schema_version(&json!({ "schema_version": -1 })).unwrap_err().error_type, InvalidSchemaVersion);
+    }
+
+    #[test]
+    fn newer_schema_version_is_rejected() {
+        let mut document = json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+
+        assert_eq!(
+            migrate(&mut document).unwrap_err().error_type,
+            NewerSchemaVersion(CURRENT_SCHEMA_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn document_is_migrated_up_to_current_version() {
+        let mut document = json!({ "package_categories": [] });
+
+        assert_eq!(migrate(&mut document).unwrap(), 0);
+        assert_eq!(schema_version(&document).unwrap(), CURRENT_SCHEMA_VERSION);
+        assert_eq!(migrate(&mut document).unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn document_which_is_not_an_object_fails_to_migrate() {
+        let mut document = json!([]);
+
+        assert_eq!(migrate(&mut document).unwrap_err().error_type, MigrationFailed(0));
+    }
+
+    #[test]
+    fn config_without_schema_version_is_deserialized() {
+        let (config, version) = deserialize_config(r#"{ "package_categories": [], "package_category_hash": "" }"#).unwrap();
+
+        assert_eq!(version, 0);
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(config.package_categories.is_empty());
+    }
+
+    #[test]
+    fn default_package_index_is_migrated_to_name() {
+        let mut document = json!({
+            "package_categories": [
+                { "default_package": 1, "packages": [{ "name": "first" }, { "name": "second" }] },
+                { "default_package": 5, "packages": [{ "name": "first" }] },
+                { "default_package": null, "packages": [] },
+            ],
+        });
+
+        assert!(migrate_from_1(document.as_object_mut().unwrap()));
+        assert_eq!(document["package_categories"][0]["default_package"], json!("second"));
+        assert_eq!(document["package_categories"][1]["default_package"], Value::Null);
+        assert_eq!(document["package_categories"][2]["default_package"], Value::Null);
+    }
+
+    #[test]
+    fn category_which_is_not_an_object_fails_default_package_migration() {
+        let mut document = json!({ "package_categories": [1] });
+
+        assert!(!migrate_from_1(document.as_object_mut().unwrap()));
+    }
+
+    #[test]
+    fn plain_env_values_are_migrated_to_set_operations() {
+        let mut document = json!({
+            "package_categories": [
+                { "packages": [{ "name": "first", "envs": { "JAVA_HOME": "/opt/jdk" } }, { "name": "second" }] },
+            ],
+        });
+
+        assert!(migrate_from_2(document.as_object_mut().unwrap()));
+        assert_eq!(
+            document["package_categories"][0]["packages"][0]["envs"]["JAVA_HOME"],
+            json!({ "operation": "set", "value": "/opt/jdk", "separator": null })
+        );
+        assert!(document["package_categories"][0]["packages"][1].get("envs").is_none());
+    }
+
+    #[test]
+    fn env_value_which_is_not_a_string_fails_env_migration() {
+        let mut document = json!({
+            "package_categories": [{ "packages": [{ "name": "first", "envs": { "JAVA_HOME": 1 } }] }],
+        });
+
+        assert!(!migrate_from_2(document.as_object_mut().unwrap()));
+    }
+
+    #[test]
+    fn packages_are_migrated_without_home() {
+        let mut document = json!({
+            "package_categories": [
+                { "packages": [{ "name": "first" }, { "name": "second", "home": "/opt/jdk" }] },
+                { "name": "without packages" },
+            ],
+        });
+
+        assert!(migrate_from_3(document.as_object_mut().unwrap()));
+        assert_eq!(document["package_categories"][0]["packages"][0]["home"], Value::Null);
+        assert_eq!(document["package_categories"][0]["packages"][1]["home"], json!("/opt/jdk"));
+    }
+
+    #[test]
+    fn package_which_is_not_an_object_fails_home_migration() {
+        let mut document = json!({ "package_categories": [{ "packages": ["first"] }] });
+
+        assert!(!migrate_from_3(document.as_object_mut().unwrap()));
+    }
+}
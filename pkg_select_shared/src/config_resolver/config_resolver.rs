@@ -2,11 +2,17 @@ use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectory
 use crate::config_resolver::category_resolver::CategoryResolver;
 use crate::config_resolver::package_resolver::PackageResolver;
 use crate::config_resolver::traits::config_path::ConfigPath;
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 
 pub struct ConfigResolver {
     pub category_resolver: CategoryResolver,
     pub package_resolver: PackageResolver,
+    /**
+       Hash of config read by the first resolve or written by the latest update.
+       Config changed from it by another process is never overwritten, so that its changes aren't lost.
+    **/
+    pub(crate) resolved_hash: RefCell<Option<String>>,
 }
 
 impl Default for ConfigResolver {
@@ -14,12 +20,13 @@ impl Default for ConfigResolver {
         ConfigResolver {
             category_resolver: CategoryResolver {},
             package_resolver: PackageResolver {},
+            resolved_hash: RefCell::new(None),
         }
     }
 }
 
 impl ConfigResolver {
-    pub(crate) fn config_path(&self) -> Result<PathBuf, SerdeIODirectoryError> {
+    pub fn config_path(&self) -> Result<PathBuf, SerdeIODirectoryError> {
         let project_dir = match self.project_dir() {
             Ok(value) => value,
             Err(error) => {
@@ -31,4 +38,12 @@ impl ConfigResolver {
             .join(project_dir)
             .join(self.config_file_name()))
     }
+
+    pub(crate) fn lock_path(&self, config_path: &Path) -> PathBuf {
+        config_path.with_file_name(format!("{}.lock", self.config_file_name()))
+    }
+
+    pub(crate) fn index_path(&self, config_path: &Path) -> PathBuf {
+        config_path.with_file_name(format!("{}.index", self.config_file_name()))
+    }
 }
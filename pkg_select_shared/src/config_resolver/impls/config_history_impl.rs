@@ -0,0 +1,157 @@
+use crate::common_models::models::config_history::ConfigHistoryEntry;
+use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
+use crate::common_models::models::runtime_config::RuntimeConfig;
+use crate::config_resolver::config_lock::write_atomically;
+use crate::config_resolver::config_migration::deserialize_config;
+use crate::config_resolver::config_resolver::ConfigResolver;
+use crate::config_resolver::traits::config_history::{ConfigHistory, HISTORY_LIMIT};
+use crate::config_resolver::traits::config_path::ConfigPath;
+use crate::PathPop;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl ConfigHistory for ConfigResolver {
+    fn history(&self) -> Result<Vec<ConfigHistoryEntry>, SerdeIODirectoryError> {
+        let history_path = match self.history_path() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+
+        if !history_path.exists() {
+            return Ok(vec![]);
+        }
+
+        let read_dir = match fs::read_dir(&history_path) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        };
+
+        let mut entries: Vec<ConfigHistoryEntry> = vec![];
+
+        for value in read_dir {
+            let path = match value {
+                Ok(value2) => value2.path(),
+                Err(error) => {
+                    return Err(SerdeIODirectoryError::IOError(error));
+                }
+            };
+
+            match parse_entry(path) {
+                None => {}
+                Some(value2) => entries.push(value2),
+            }
+        }
+
+        entries.sort_by_key(| value | Reverse(value.id));
+
+        Ok(entries)
+    }
+
+    fn resolve_history(&self, entry: &ConfigHistoryEntry) -> Result<RuntimeConfig, SerdeIODirectoryError> {
+        let raw_data = match fs::read_to_string(&entry.path) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        };
+
+        deserialize_config(&raw_data).map(| (value, _) | value)
+    }
+}
+
+impl ConfigResolver {
+    pub(crate) fn history_path(&self) -> Result<PathBuf, SerdeIODirectoryError> {
+        let config_path = match self.config_path() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+
+        Ok(config_path.pop_path().join(self.history_dir_name()))
+    }
+
+    /**
+       Save config which is about to be overwritten, and remove the oldest entries over [HISTORY_LIMIT].
+       It must be called while config lock is held.
+    **/
+    pub(crate) fn record_history(&self, raw_data: &str) -> Result<(), SerdeIODirectoryError> {
+        let entries = match self.history() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+        let history_path = match self.history_path() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+
+        match fs::create_dir_all(&history_path) {
+            Ok(_) => {}
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        }
+
+        let id = entries.first().map_or(1, | value | value.id + 1);
+        let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(value) => value.as_secs(),
+            Err(_) => 0,
+        };
+        let entry_path = history_path.join(format!("{}-{}.json", id, timestamp));
+
+        match write_atomically(&entry_path, raw_data) {
+            Ok(_) => {}
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        }
+
+        for value in entries.iter().skip(HISTORY_LIMIT - 1) {
+            match fs::remove_file(&value.path) {
+                Ok(_) => {}
+                Err(error) => {
+                    return Err(SerdeIODirectoryError::IOError(error));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+   Entry file is named as "<id>-<timestamp>.json".
+**/
+fn parse_entry(path: PathBuf) -> Option<ConfigHistoryEntry> {
+    if !path.extension().is_some_and(| value | value.eq("json")) {
+        return None;
+    }
+
+    let file_stem = path.file_stem()?.to_string_lossy().to_string();
+    let (id, timestamp) = file_stem.split_once('-')?;
+    let id: u64 = id.parse().ok()?;
+    let timestamp: u64 = timestamp.parse().ok()?;
+
+    let package_category_hash = fs::read_to_string(&path).ok()
+        .and_then(| value | serde_json::from_str::<Value>(&value).ok())
+        .and_then(| value | value.get("package_category_hash").and_then(| value2 | value2.as_str()).map(| value2 | value2.to_string()))
+        .unwrap_or_default();
+
+    Some(ConfigHistoryEntry {
+        id,
+        timestamp,
+        package_category_hash,
+        path,
+    })
+}
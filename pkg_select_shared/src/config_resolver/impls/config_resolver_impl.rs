@@ -1,11 +1,14 @@
 use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
-use crate::common_models::models::runtime_config::RuntimeConfig;
+use crate::common_models::models::runtime_config::{RuntimeConfig, CURRENT_SCHEMA_VERSION};
+use crate::config_resolver::config_lock::{write_atomically, ConfigLock};
+use crate::config_resolver::config_migration::deserialize_config;
 use crate::config_resolver::config_resolver::ConfigResolver;
 use crate::config_resolver::traits::config_path::ConfigPath;
 use crate::config_resolver::traits::config_persistence::ConfigPersistence;
-use crate::{project_dirs, PathPop};
-use std::{env, fs};
+use crate::{log_warn, print_dbg_on_debug, project_dirs, string_to_hash, sudo_user_home_dir, PathPop};
+use std::{fs, io};
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 impl ConfigPersistence for ConfigResolver {
     fn exists(&self) -> Result<bool, SerdeIODirectoryError> {
@@ -39,18 +42,65 @@ impl ConfigPersistence for ConfigResolver {
                 return Err(error);
             }
         };
-        let raw_data = match fs::read_to_string(path) {
+        let raw_data = match fs::read_to_string(&path) {
             Ok(value) => value,
             Err(error) => {
                 return Err(SerdeIODirectoryError::IOError(error));
             }
         };
+        // Later resolves such as the one comparing changes on commit must not take changes of others as read.
+        if self.resolved_hash.borrow().is_none() {
+            *self.resolved_hash.borrow_mut() = Some(string_to_hash(&raw_data));
+        }
+
+        // Config of older schema is migrated in memory only, since runners must not write config.
+        // CLI persists it by ConfigPersistence::migrate.
+        match deserialize_config(&raw_data) {
+            Ok((value, _)) => Ok(value),
+            Err(error) => Err(error),
+        }
+    }
 
-        match serde_json::from_str(&raw_data) {
-            Ok(value) => Ok(value),
+    fn migrate(&self) -> Result<Option<PathBuf>, SerdeIODirectoryError> {
+        let path = match self.config_path() {
+            Ok(value) => value,
             Err(error) => {
-                return Err(SerdeIODirectoryError::SerdeError(error));
+                return Err(error);
+            }
+        };
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let raw_data = match fs::read_to_string(&path) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        };
+        let (config, version) = match deserialize_config(&raw_data) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+
+        if version >= CURRENT_SCHEMA_VERSION {
+            return Ok(None);
+        }
+
+        // Migrated config is written only after the original one is backed up.
+        let backup_path = match backup_config(&path, version, &raw_data) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
             }
+        };
+
+        match self.update(&config) {
+            Ok(_) => Ok(Some(backup_path)),
+            Err(error) => Err(error),
         }
     }
 
@@ -89,13 +139,54 @@ impl ConfigPersistence for ConfigResolver {
             }
         }
 
-        dbg!(&path);
-        dbg!(&raw_data);
+        print_dbg_on_debug!(&path);
 
-        return match fs::write(&path, &raw_data) {
-            Ok(_) => Ok(()),
-            Err(error) => Err(SerdeIODirectoryError::IOError(error)),
+        let _lock = match ConfigLock::acquire(self.lock_path(&path)) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
         };
+
+        // Config doesn't exist before it's saved for the first time, so there is nothing to compare or keep.
+        if let Ok(value) = fs::read_to_string(&path) {
+            let is_changed = match self.resolved_hash.borrow().as_ref() {
+                None => false,
+                Some(value2) => !value2.eq(&string_to_hash(&value)),
+            };
+
+            if is_changed {
+                return Err(SerdeIODirectoryError::IOError(io::Error::other(
+                    format!(
+                        "{} is changed by another process after it was read. Run the command again to apply changes on top of it.",
+                        path.to_string_lossy()
+                    ),
+                )));
+            }
+
+            if !value.eq(&raw_data) {
+                match self.record_history(&value) {
+                    Ok(_) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        match write_atomically(&path, &raw_data) {
+            Ok(_) => {}
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        }
+
+        *self.resolved_hash.borrow_mut() = Some(string_to_hash(&raw_data));
+
+        match self.write_index(&path, config) {
+            Ok(_) => {}
+            Err(error) => log_warn!("couldn't update runner index: {}", error),
+        }
+
+        Ok(())
     }
 
     fn reset(&self) -> Result<RuntimeConfig, SerdeIODirectoryError> {
@@ -110,14 +201,9 @@ impl ConfigPersistence for ConfigResolver {
 
 impl ConfigPath for ConfigResolver {
     fn project_dir(&self) -> Result<PathBuf, SerdeIODirectoryError> {
-        if cfg!(target_os = "linux") {
-            let vars: Vec<(String, String)> = env::vars().collect();
-
-            for (key, value) in vars.iter() {
-                if key.to_lowercase().eq("sudo_user") {
-                    return Ok(PathBuf::from(format!("/home/{}/.config", value)));
-                }
-            }
+        match sudo_user_home_dir() {
+            None => {}
+            Some(value) => return Ok(value.join(".config")),
         }
 
         let project_dir = match project_dirs() {
@@ -130,3 +216,20 @@ impl ConfigPath for ConfigResolver {
         return Ok(project_dir.config_dir().to_path_buf());
     }
 }
+
+fn backup_config(path: &PathBuf, version: u32, raw_data: &str) -> Result<PathBuf, SerdeIODirectoryError> {
+    let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(value) => value.as_secs(),
+        Err(_) => 0,
+    };
+    let file_name = match path.file_name() {
+        None => String::from("config.json"),
+        Some(value) => value.to_string_lossy().to_string(),
+    };
+    let backup_path = path.pop_path().join(format!("{}.v{}-{}.bak", file_name, version, timestamp));
+
+    match write_atomically(&backup_path, raw_data) {
+        Ok(_) => Ok(backup_path),
+        Err(error) => Err(SerdeIODirectoryError::IOError(error)),
+    }
+}
@@ -1,3 +1,5 @@
 pub mod category_resolver_impl;
+pub mod config_history_impl;
 pub mod config_resolver_impl;
 pub mod package_resolver_impl;
+pub mod runner_index_persistence_impl;
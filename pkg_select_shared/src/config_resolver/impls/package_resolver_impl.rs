@@ -1,11 +1,20 @@
+use crate::common_models::models::configurations::package_category::PackageCategory;
 use crate::common_models::models::configurations::runnable_package::RunnablePackage;
+use crate::common_models::models::errors::package_override::PackageOverrideError;
+use crate::common_models::models::errors::package_override::Type;
+use crate::common_models::models::errors::package_override::Type::{BinderNotFound, BinderNotInPackage, InvalidEnvOverride, PackageNotFound, PackageNotInCategory};
+use crate::common_models::models::errors::project_selection::ProjectSelectionError;
+use crate::common_models::models::errors::project_selection::Type::UnknownPackage;
+use crate::common_models::models::package_evaluation::{CandidatePackage, PackageEvaluation, SelectionReason, SkipReason};
+use crate::common_models::models::project_selection::ProjectSelection;
 use crate::common_models::models::runtime_config::RuntimeConfig;
 use crate::config_resolver::package_resolver::PackageResolver;
 use crate::config_resolver::traits::package_search::PackageSearch;
-use std::cmp::Ordering;
+use std::env;
 use std::ffi::OsStr;
-use std::path::PathBuf;
-use crate::PathPop;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use crate::{match_path_pattern, PathPop};
 
 impl PackageSearch for PackageResolver {
     fn find_by_name<'t>(
@@ -39,43 +48,106 @@ impl PackageSearch for PackageResolver {
         process_path: &'t PathBuf,
         working_path: &'t PathBuf,
     ) -> Option<&'t RunnablePackage> {
+        self.evaluate_by_paths(config, process_path, working_path)
+            .selected
+            .map(| (value, _) | value.package)
+    }
+
+    fn evaluate_by_paths<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+        working_path: &'t Path,
+    ) -> PackageEvaluation<'t> {
         let process_filename = match process_path.is_file() {
             true => process_path.file_name(),
             false => None,
         };
-        let process_path_without_filename = process_path.pop_path();
+        let candidates =
+            find_all_by_process_internal(config, process_path.pop_path(), process_filename);
 
-        let mut found_packages =
-            find_all_by_process_internal(config, process_path_without_filename, process_filename);
+        let (project_selection, project_selection_errors) = ProjectSelection::find(working_path);
 
-        if found_packages.is_empty() {
-            return None;
+        evaluate_candidates(
+            candidates,
+            working_path,
+            project_selection,
+            project_selection_errors,
+        )
+    }
+
+    fn find_by_override<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+        package_name: &str,
+    ) -> Result<CandidatePackage<'t>, PackageOverrideError> {
+        let process_filename = match process_path.is_file() {
+            true => process_path.file_name(),
+            false => None,
+        };
+        let candidates =
+            find_all_by_process_internal(config, process_path.pop_path(), process_filename);
+
+        if candidates.is_empty() {
+            return Err(PackageOverrideError {
+                error_type: BinderNotFound(process_path.to_string_lossy().to_string())
+            });
         }
 
-        found_packages.sort_unstable_by(|a, b| {
-            let a_result = a
-                .included_paths
-                .iter()
-                .find(|value| PathBuf::from(value).eq(working_path));
-            let b_result = b
-                .included_paths
-                .iter()
-                .find(|value| PathBuf::from(value).eq(working_path));
-
-            if a_result.is_none() && b_result.is_none() {
-                return Ordering::Equal;
+        match candidates.iter().find(| value | value.package.name.eq(package_name)) {
+            None => {}
+            Some(value) => {
+                return Ok(value.clone());
             }
-            return if b_result.is_some() {
-                Ordering::Greater
-            } else {
-                Ordering::Less
+        }
+
+        let categories = owning_categories(&candidates);
+
+        Err(PackageOverrideError {
+            error_type: override_error_type(config, &categories, &candidates, package_name)
+        })
+    }
+
+    fn find_by_env_override<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+    ) -> Result<Option<(CandidatePackage<'t>, String)>, PackageOverrideError> {
+        let process_filename = match process_path.is_file() {
+            true => process_path.file_name(),
+            false => None,
+        };
+        let candidates =
+            find_all_by_process_internal(config, process_path.pop_path(), process_filename);
+
+        for value in owning_categories(&candidates).iter() {
+            let key = value.override_env_key();
+            let package_name = match env::var(&key) {
+                Ok(value2) if !value2.is_empty() => value2,
+                _ => continue,
             };
-        });
+            let applied = format!("{}={}", key, package_name);
+
+            let found = candidates.iter().find(| value2 | {
+                ptr::eq(value2.category, *value) && value2.package.name.eq(&package_name)
+            });
+
+            match found {
+                None => {}
+                Some(value2) => {
+                    return Ok(Some((value2.clone(), applied)));
+                }
+            }
+
+            let error_type = override_error_type(config, &vec![*value], &candidates, &package_name);
 
-        match found_packages.get(0) {
-            None => None,
-            Some(value) => Some(value),
+            return Err(PackageOverrideError {
+                error_type: InvalidEnvOverride(applied, Box::new(error_type))
+            });
         }
+
+        Ok(None)
     }
 
     fn find_all_by_path<'t>(
@@ -89,47 +161,339 @@ impl PackageSearch for PackageResolver {
         };
         let path_without_filename = path.pop_path();
 
-        find_all_by_process_internal(config, path_without_filename, filename)
+        find_all_by_process_internal(config, path_without_filename, filename).iter()
+            .map(| value | value.package)
+            .collect()
+    }
+}
+
+fn owning_categories<'t>(candidates: &Vec<CandidatePackage<'t>>) -> Vec<&'t PackageCategory> {
+    let mut categories: Vec<&PackageCategory> = vec![];
+
+    for value in candidates.iter() {
+        if !categories.iter().any(| value2 | ptr::eq(*value2, value.category)) {
+            categories.push(value.category);
+        }
+    }
+
+    categories
+}
+
+fn override_error_type(
+    config: &RuntimeConfig,
+    categories: &Vec<&PackageCategory>,
+    candidates: &Vec<CandidatePackage>,
+    package_name: &str,
+) -> Type {
+    let in_categories = categories.iter()
+        .any(| value | value.packages.iter().any(| value2 | value2.name.eq(package_name)));
+    let in_config = config.package_categories.iter()
+        .any(| value | value.packages.iter().any(| value2 | value2.name.eq(package_name)));
+
+    if in_categories {
+        let binder_name = candidates.first()
+            .map(| value | value.binder.target_name.to_string())
+            .unwrap_or_default();
+
+        return BinderNotInPackage(package_name.to_string(), binder_name);
+    }
+
+    if in_config {
+        let names: Vec<String> = categories.iter()
+            .map(| value | value.name.to_string())
+            .collect();
+
+        return PackageNotInCategory(package_name.to_string(), names.join(", "));
     }
+
+    PackageNotFound(package_name.to_string())
 }
 
 fn find_all_by_process_internal<'t>(
     config: &'t RuntimeConfig,
     path_without_filename: PathBuf,
     filename: Option<&'t OsStr>,
-) -> Vec<&'t RunnablePackage> {
-    let mut found_packages: Vec<&RunnablePackage> = vec![];
+) -> Vec<CandidatePackage<'t>> {
+    let mut found_packages: Vec<CandidatePackage> = vec![];
+
+    let filename = match filename {
+        None => {
+            return found_packages;
+        }
+        Some(value) => value.to_string_lossy().to_string(),
+    };
 
     for value in config.package_categories.iter() {
         for value2 in value.packages.iter() {
             let found_binder = value2.binders.iter().find(|value3| {
                 let execution_path = PathBuf::from(&value3.execution_path);
 
-                if !(&path_without_filename).eq(&execution_path) {
-                    return false;
+                path_without_filename.eq(&execution_path) && filename.eq(&value3.target_name)
+            });
+
+            match found_binder {
+                None => {}
+                Some(value3) => {
+                    found_packages.push(CandidatePackage {
+                        category: value,
+                        package: value2,
+                        binder: value3,
+                    });
                 }
+            }
+        }
+    }
 
-                match &filename {
-                    None => {
-                        return false;
-                    },
-                    Some(value) => {
-                        if !value.to_string_lossy().eq(&value3.target_name) {
-                            return false;
-                        }
-                    },
+    found_packages
+}
+
+fn evaluate_candidates<'t>(
+    candidates: Vec<CandidatePackage<'t>>,
+    working_path: &Path,
+    project_selection: Option<ProjectSelection>,
+    project_selection_errors: Vec<ProjectSelectionError>,
+) -> PackageEvaluation<'t> {
+    let mut evaluation = PackageEvaluation {
+        project_selection_errors,
+        ..PackageEvaluation::default()
+    };
+    let mut remaining: Vec<CandidatePackage> = vec![];
+
+    for value in owning_categories(&candidates).iter() {
+        match unknown_project_package(&project_selection, value) {
+            None => {}
+            Some(value2) => evaluation.project_selection_errors.push(value2),
+        }
+    }
+
+    for value in candidates.into_iter() {
+        match find_excluded_path(value.package, working_path) {
+            None => {
+                remaining.push(value);
+            }
+            Some(value2) => {
+                let reason = SkipReason::ExcludedPath(value2.to_string());
+
+                evaluation.skipped.push((value, reason));
+            }
+        }
+    }
+
+    let included_paths: Vec<Option<(&String, usize)>> = remaining.iter()
+        .map(| value | find_matched_path(&value.package.included_paths, working_path))
+        .collect();
+    let selected_by_project = remaining.iter()
+        .position(| value | is_selected_by_project(&project_selection, value.category, value.package));
+    let selected = match selected_by_project {
+        Some(value) => {
+            let path = project_selection.as_ref().unwrap().path.to_string_lossy().to_string();
+
+            Some((value, SelectionReason::ProjectFile(path)))
+        }
+        None => match find_most_specific(&included_paths) {
+            Some(value) => {
+                Some((value, SelectionReason::IncludedPath(included_paths[value].unwrap().0.to_string())))
+            }
+            None => {
+                match remaining.iter().position(| value | is_default_package(value.category, value.package)) {
+                    Some(value) => Some((value, SelectionReason::DefaultPackage)),
+                    None if remaining.is_empty() => None,
+                    None => Some((0, SelectionReason::FirstCandidate)),
                 }
+            }
+        },
+    };
 
-                value2.excluded_paths.iter()
-                    .find(| value | path_without_filename.eq(&PathBuf::from(value)))
-                    .is_some()
-            });
+    for (index, value) in remaining.into_iter().enumerate() {
+        match &selected {
+            Some((value2, reason)) if index.eq(value2) => {
+                evaluation.selected = Some((value, reason.clone()));
+            }
+            _ => {
+                let reason = match included_paths[index].is_some()
+                    || is_selected_by_project(&project_selection, value.category, value.package)
+                    || is_default_package(value.category, value.package) {
+                    true => SkipReason::OutPrioritized,
+                    false => SkipReason::NotDefaultPackage,
+                };
 
-            if found_binder.is_some() {
-                let _ = &found_packages.push(&value2);
+                evaluation.skipped.push((value, reason));
             }
         }
     }
 
-    found_packages
+    evaluation.project_selection = project_selection;
+
+    evaluation
+}
+
+/**
+   Returns the most specific path matched with working path, and its specificity.
+**/
+fn find_matched_path<'t>(paths: &'t [String], working_path: &Path) -> Option<(&'t String, usize)> {
+    let mut matched: Option<(&String, usize)> = None;
+
+    for value in paths.iter() {
+        match (match_path_pattern(value, working_path), matched) {
+            (None, _) => {}
+            (Some(value2), Some((_, value3))) if value2 <= value3 => {}
+            (Some(value2), _) => {
+                matched = Some((value, value2));
+            }
+        }
+    }
+
+    matched
+}
+
+/**
+   Returns the excluded path matched with working path, unless an included path of the package is more specific.
+**/
+fn find_excluded_path<'t>(package: &'t RunnablePackage, working_path: &Path) -> Option<&'t String> {
+    let (excluded_path, excluded_specificity) = find_matched_path(&package.excluded_paths, working_path)?;
+
+    match find_matched_path(&package.included_paths, working_path) {
+        Some((_, value)) if value > excluded_specificity => None,
+        _ => Some(excluded_path),
+    }
+}
+
+/**
+   Returns index of the most specific included path. The earliest one is preferred on tie.
+**/
+fn find_most_specific(included_paths: &Vec<Option<(&String, usize)>>) -> Option<usize> {
+    let mut found: Option<(usize, usize)> = None;
+
+    for (index, value) in included_paths.iter().enumerate() {
+        match (value, found) {
+            (None, _) => {}
+            (Some((_, value2)), Some((_, value3))) if *value2 <= value3 => {}
+            (Some((_, value2)), _) => {
+                found = Some((index, *value2));
+            }
+        }
+    }
+
+    found.map(| (value, _) | value)
+}
+
+fn is_default_package(category: &PackageCategory, package: &RunnablePackage) -> bool {
+    match &category.default_package {
+        None => false,
+        Some(value) => value.eq(&package.name),
+    }
+}
+
+/**
+   Package named by project file for given category, if the category doesn't have it.
+**/
+fn unknown_project_package(
+    project_selection: &Option<ProjectSelection>,
+    category: &PackageCategory,
+) -> Option<ProjectSelectionError> {
+    let selection = project_selection.as_ref()?;
+    let package_name = selection.package_name(&category.name)?;
+
+    if category.packages.iter().any(| value | value.name.eq(package_name)) {
+        return None;
+    }
+
+    Some(ProjectSelectionError {
+        error_type: UnknownPackage(
+            selection.path.to_string_lossy().to_string(),
+            category.name.to_string(),
+            package_name.to_string(),
+        )
+    })
+}
+
+fn is_selected_by_project(
+    project_selection: &Option<ProjectSelection>,
+    category: &PackageCategory,
+    package: &RunnablePackage,
+) -> bool {
+    match project_selection.as_ref().and_then(| value | value.package_name(&category.name)) {
+        None => false,
+        Some(value) => value.eq(&package.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common_models::models::configurations::target_binder::TargetBinder;
+
+    #[test]
+    fn matched_path_is_the_most_specific_one() {
+        let paths = vec![
+            format!("/home/**"),
+            format!("/home/me/work/service"),
+            format!("/home/me/work"),
+        ];
+
+        let (path, _) = find_matched_path(&paths, &PathBuf::from("/home/me/work/service/src")).unwrap();
+        assert_eq!(path, "/home/me/work/service");
+
+        let (path, _) = find_matched_path(&paths, &PathBuf::from("/home/me/other")).unwrap();
+        assert_eq!(path, "/home/**");
+
+        assert!(find_matched_path(&paths, &PathBuf::from("/opt")).is_none());
+    }
+
+    #[test]
+    fn most_specific_included_path_wins_and_earliest_on_tie() {
+        let first = "/home/me".to_string();
+        let second = "/home/me/work".to_string();
+        let third = "/home/you/work".to_string();
+
+        assert_eq!(find_most_specific(&vec![Some((&first, 6)), Some((&second, 8)), None]), Some(1));
+        assert_eq!(find_most_specific(&vec![None, Some((&second, 8)), Some((&third, 8))]), Some(1));
+        assert_eq!(find_most_specific(&vec![None, None]), None);
+    }
+
+    fn binder() -> TargetBinder {
+        TargetBinder {
+            target_name: "java".to_string(),
+            target_path: "/opt/jdk/bin".to_string(),
+            execution_path: "/usr/local/bin".to_string(),
+        }
+    }
+
+    #[test]
+    fn package_is_skipped_when_working_path_is_under_excluded_glob() {
+        let mut legacy = RunnablePackage::default();
+        legacy.name = "legacy".to_string();
+        legacy.binders = vec![binder()];
+        legacy.excluded_paths = vec!["/home/*/legacy-*/**".to_string()];
+
+        let mut other = RunnablePackage::default();
+        other.name = "other".to_string();
+        other.binders = vec![binder()];
+
+        let mut category = PackageCategory::default();
+        category.name = "jdk".to_string();
+        category.packages = vec![legacy, other];
+        category.default_package = Some("legacy".to_string());
+
+        let candidates = category.packages.iter()
+            .map(| value | CandidatePackage { category: &category, package: value, binder: &value.binders[0] })
+            .collect();
+        let evaluation = evaluate_candidates(candidates, Path::new("/home/me/legacy-app/src"), None, vec![]);
+
+        assert_eq!(evaluation.selected.as_ref().unwrap().0.package.name, "other");
+        assert_eq!(evaluation.skipped[0].1, SkipReason::ExcludedPath("/home/*/legacy-*/**".to_string()));
+    }
+
+    #[test]
+    fn more_specific_included_path_wins_over_excluded_path() {
+        let mut package = RunnablePackage::default();
+        package.included_paths = vec!["/home/me/legacy-app/service".to_string()];
+        package.excluded_paths = vec!["/home/*/legacy-*/**".to_string()];
+
+        assert!(find_excluded_path(&package, Path::new("/home/me/legacy-app/service/src")).is_none());
+        assert_eq!(
+            find_excluded_path(&package, Path::new("/home/me/legacy-app/web")),
+            Some(&"/home/*/legacy-*/**".to_string())
+        );
+    }
 }
@@ -0,0 +1,141 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
+use crate::common_models::models::runner_index::{ConfigStamp, RunnerIndex};
+use crate::common_models::models::runtime_config::RuntimeConfig;
+use crate::config_resolver::config_lock::{write_atomically, ConfigLock};
+use crate::config_resolver::config_resolver::ConfigResolver;
+use crate::config_resolver::traits::runner_index_persistence::RunnerIndexPersistence;
+use crate::{print_dbg_on_debug, runner_file_path, string_to_hash};
+
+impl RunnerIndexPersistence for ConfigResolver {
+    fn update_index(&self, config: &RuntimeConfig) -> Result<(), SerdeIODirectoryError> {
+        let path = match self.config_path() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(error);
+            }
+        };
+
+        let _lock = match ConfigLock::acquire(self.lock_path(&path)) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::IOError(error));
+            }
+        };
+
+        // Index of given config must not be paired with config written by another process after it was read.
+        let is_changed = match (fs::read_to_string(&path), self.resolved_hash.borrow().as_ref()) {
+            (Ok(value), Some(value2)) => !value2.eq(&string_to_hash(&value)),
+            _ => true,
+        };
+
+        if is_changed {
+            return Err(SerdeIODirectoryError::IOError(io::Error::other(
+                format!("{} is changed by another process after it was read.", path.to_string_lossy()),
+            )));
+        }
+
+        self.write_index(&path, config)
+    }
+
+    fn resolve_index(&self) -> Option<RunnerIndex> {
+        let path = self.config_path().ok()?;
+
+        read_index(&path, &self.index_path(&path))
+    }
+}
+
+/**
+   Index at given path, if it is made from config at given path.
+   Config is only stat, since reading it on every execution of runners is what the index saves.
+**/
+fn read_index(config_path: &Path, index_path: &Path) -> Option<RunnerIndex> {
+    let raw_data = fs::read_to_string(index_path).ok()?;
+    let index: RunnerIndex = serde_json::from_str(&raw_data).ok()?;
+
+    if ConfigStamp::of(config_path).as_ref() != Some(&index.config_stamp) {
+        print_dbg_on_debug!("runner index is stale.");
+        return None;
+    }
+
+    Some(index)
+}
+
+impl ConfigResolver {
+    /**
+       Write index of config just written to given path. Caller must hold [ConfigLock],
+       so that the index is stamped with that config and not with one written by another process.
+    **/
+    pub(crate) fn write_index(&self, config_path: &Path, config: &RuntimeConfig) -> Result<(), SerdeIODirectoryError> {
+        let config_stamp = match ConfigStamp::of(config_path) {
+            None => {
+                return Err(SerdeIODirectoryError::IOError(
+                    io::Error::new(io::ErrorKind::NotFound, "couldn't get metadata of config.")
+                ));
+            }
+            Some(value) => value,
+        };
+
+        // Compact on purpose, since it is read on every execution of runners.
+        let raw_data = match serde_json::to_string(&RunnerIndex::build(config, config_stamp, runner_file_path().as_ref())) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(SerdeIODirectoryError::SerdeError(error));
+            }
+        };
+
+        match write_atomically(&self.index_path(config_path), &raw_data) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(SerdeIODirectoryError::IOError(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::process;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("pkg-select-{}-{}", name, process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        directory
+    }
+
+    fn write_index(config_path: &Path, index_path: &Path) {
+        let index = RunnerIndex::build(&RuntimeConfig::default(), ConfigStamp::of(config_path).unwrap(), None);
+
+        fs::write(index_path, serde_json::to_string(&index).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn index_is_read_without_parsing_config() {
+        let directory = temp_dir("index-unparsed");
+        let config_path = directory.join("config.json");
+        let index_path = directory.join("config.json.index");
+
+        fs::write(&config_path, "not json").unwrap();
+        write_index(&config_path, &index_path);
+
+        assert!(read_index(&config_path, &index_path).is_some());
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn index_of_replaced_config_is_stale() {
+        let directory = temp_dir("index-stale");
+        let config_path = directory.join("config.json");
+        let index_path = directory.join("config.json.index");
+
+        fs::write(&config_path, "{}").unwrap();
+        write_index(&config_path, &index_path);
+        write_atomically(&config_path, &"{ }".to_string()).unwrap();
+
+        assert!(read_index(&config_path, &index_path).is_none());
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
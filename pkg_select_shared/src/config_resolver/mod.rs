@@ -1,4 +1,6 @@
 pub mod category_resolver;
+pub mod config_lock;
+pub mod config_migration;
 pub mod config_resolver;
 pub mod impls;
 pub mod package_resolver;
@@ -0,0 +1,20 @@
+use crate::common_models::models::config_history::ConfigHistoryEntry;
+use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
+use crate::common_models::models::runtime_config::RuntimeConfig;
+
+pub const HISTORY_LIMIT: usize = 20;
+
+/**
+   [ConfigHistory] trait provides access to configs which were overwritten before.
+   Entries are kept in history directory next to config, and only the last [HISTORY_LIMIT] of them remain.
+**/
+pub trait ConfigHistory {
+    /**
+       List every entry in history, the latest one comes first.
+    **/
+    fn history(&self) -> Result<Vec<ConfigHistoryEntry>, SerdeIODirectoryError>;
+    /**
+       Read config of given entry. Entries from older schema versions are migrated in memory.
+    **/
+    fn resolve_history(&self, entry: &ConfigHistoryEntry) -> Result<RuntimeConfig, SerdeIODirectoryError>;
+}
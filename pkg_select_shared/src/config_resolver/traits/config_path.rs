@@ -6,4 +6,7 @@ pub trait ConfigPath {
     fn config_file_name(&self) -> String {
         "config.json".to_string()
     }
+    fn history_dir_name(&self) -> String {
+        "history".to_string()
+    }
 }
@@ -1,9 +1,20 @@
 use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
 use crate::common_models::models::runtime_config::RuntimeConfig;
+use std::path::PathBuf;
 
 pub trait ConfigPersistence {
     fn exists(&self) -> Result<bool, SerdeIODirectoryError>;
+    /**
+       Read config, migrating it to the current schema in memory if it is older.
+       It is used by runners as well, so it never writes migrated config.
+    **/
     fn resolve(&self) -> Result<RuntimeConfig, SerdeIODirectoryError>;
+    /**
+       Write config migrated to the current schema after backing up the original one.
+       Returns the backup path, or nothing if config is already up to date.
+       Only CLI should call it, so that running a binder never rewrites config.
+    **/
+    fn migrate(&self) -> Result<Option<PathBuf>, SerdeIODirectoryError>;
     fn update(&self, config: &RuntimeConfig) -> Result<(), SerdeIODirectoryError>;
     fn reset(&self) -> Result<RuntimeConfig, SerdeIODirectoryError>;
 }
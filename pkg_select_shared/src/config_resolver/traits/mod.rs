@@ -1,4 +1,6 @@
 pub mod category_search;
+pub mod config_history;
 pub mod config_path;
 pub mod config_persistence;
 pub mod package_search;
+pub mod runner_index_persistence;
@@ -3,8 +3,10 @@ use crate::common_models::models::configurations::package_category::PackageCateg
 use crate::common_models::models::configurations::runnable_package::RunnablePackage;
 #[allow(unused_imports)]
 use crate::common_models::models::configurations::target_binder::TargetBinder;
+use crate::common_models::models::errors::package_override::PackageOverrideError;
+use crate::common_models::models::package_evaluation::{CandidatePackage, PackageEvaluation};
 use crate::common_models::models::runtime_config::RuntimeConfig;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /**
    [PackageSearch] trait provides find options to get [RunnablePackage]
@@ -27,14 +29,18 @@ pub trait PackageSearch {
 
        "process path" indicates where process is located.
        "working path" indicates where working directory is (similar to pwd).
-       All condition except rule 5 assumes process path is matched with execution_path in one of [TargetBinder] at least.
+       All condition except rule 6 assumes process path is matched with execution_path in one of [TargetBinder] at least.
        List numbers almost mean priority so the less number will impact greater than the bigger ones on selection.
 
+       0. If `.pkg-select.json` is found by walking up from working path and it names a [RunnablePackage] for its [PackageCategory], it will be selected.
        1. If working path is in included_paths in one [RunnablePackage] only, it will be selected.
-       2. If there is more than one matched, the earliest one has been created will be selected.
+          A path matches its descendants as well, and glob patterns such as "/home/*/legacy-*/**" are supported.
+       2. If there is more than one matched, the most specific one will be selected. On tie, the earliest one has been created will be selected.
        3. If there is none, the default package configured in [PackageCategory] selects [RunnablePackage].
-       4. If there is none and the path is in excluded_paths in the default package, it will be *ignored*.
-       5. If none of conditions above is satisfied or there is no [TargetBinder] matches its execution_path with process path, return nothing.
+       4. If there is no default package either, the earliest one has been created will be selected.
+       5. If working path is in excluded_paths of a [RunnablePackage], it will be *ignored* by every rule above.
+          Excluded paths match the same way as included_paths, and an included path more specific than it wins.
+       6. If there is no [TargetBinder] matches its execution_path with process path, return nothing.
     **/
     fn find_by_paths<'t>(
         &self,
@@ -42,6 +48,38 @@ pub trait PackageSearch {
         process_path: &'t PathBuf,
         working_path: &'t PathBuf,
     ) -> Option<&'t RunnablePackage>;
+    /**
+       Same as [PackageSearch::find_by_paths] but returns [PackageEvaluation] describing
+       why the package is selected and why the other candidates are skipped.
+    **/
+    fn evaluate_by_paths<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+        working_path: &'t Path,
+    ) -> PackageEvaluation<'t>;
+    /**
+       Select [RunnablePackage] named explicitly such as `java +temurin-21`, bypassing every rule of [PackageSearch::find_by_paths].
+       The package must belong to the [PackageCategory] owning a [TargetBinder] matched with process path,
+       and it must have a [TargetBinder] with the same name.
+    **/
+    fn find_by_override<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+        package_name: &str,
+    ) -> Result<CandidatePackage<'t>, PackageOverrideError>;
+    /**
+       Select [RunnablePackage] named by env var of the [PackageCategory] owning a [TargetBinder] matched with process path,
+       such as `PKG_SELECT_JDK=zulu-11`, bypassing every rule of [PackageSearch::find_by_paths].
+       Returns the package with the env var applied, or nothing if no env var is set.
+       An env var naming an invalid package is an error rather than being ignored.
+    **/
+    fn find_by_env_override<'t>(
+        &self,
+        config: &'t RuntimeConfig,
+        process_path: &'t Path,
+    ) -> Result<Option<(CandidatePackage<'t>, String)>, PackageOverrideError>;
     /**
        Find all [RunnablePackage] by given "process path"
     **/
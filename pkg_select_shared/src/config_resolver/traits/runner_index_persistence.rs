@@ -0,0 +1,22 @@
+use crate::common_models::models::errors::config_resolve_combo::SerdeIODirectoryError;
+use crate::common_models::models::runner_index::RunnerIndex;
+use crate::common_models::models::runtime_config::RuntimeConfig;
+
+/**
+   [RunnerIndexPersistence] trait keeps [RunnerIndex] next to config for runners.
+**/
+pub trait RunnerIndexPersistence {
+    /**
+       Write index of given config, which must be the one resolved or updated last.
+       [ConfigPersistence::update] writes index by itself, so it is needed only when config is left unchanged.
+       Fails if config is changed by another process since then.
+
+       [ConfigPersistence::update]: crate::config_resolver::traits::config_persistence::ConfigPersistence::update
+    **/
+    fn update_index(&self, config: &RuntimeConfig) -> Result<(), SerdeIODirectoryError>;
+    /**
+       Read index only if it is made from the current config, telling by stat of config alone.
+       Otherwise returns nothing, so that config is resolved fully.
+    **/
+    fn resolve_index(&self) -> Option<RunnerIndex>;
+}
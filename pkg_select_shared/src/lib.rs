@@ -14,20 +14,52 @@ use indexmap::IndexSet;
 pub mod common_models;
 pub mod config_resolver;
 pub mod argument_parser;
+pub mod logger;
 
 #[macro_export]
-macro_rules! print_dbg_on_debug {
+macro_rules! log_error {
     ($($rest:tt)*) => {
-        #[cfg(debug_assertions)]
-        std::dbg!($($rest)*)
+        $crate::logger::logger::log($crate::logger::models::log_level::LogLevel::Error, std::format_args!($($rest)*))
+    }
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($rest:tt)*) => {
+        $crate::logger::logger::log($crate::logger::models::log_level::LogLevel::Warn, std::format_args!($($rest)*))
+    }
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($rest:tt)*) => {
+        $crate::logger::logger::log($crate::logger::models::log_level::LogLevel::Info, std::format_args!($($rest)*))
+    }
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($rest:tt)*) => {
+        $crate::logger::logger::log($crate::logger::models::log_level::LogLevel::Debug, std::format_args!($($rest)*))
+    }
+}
+
+/**
+   Same as [std::dbg] but it is logged in debug level only.
+**/
+#[macro_export]
+macro_rules! print_dbg_on_debug {
+    ($value:expr $(,)?) => {
+        if $crate::logger::logger::log_enabled($crate::logger::models::log_level::LogLevel::Debug) {
+            $crate::log_debug!("[{}:{}] {} = {:#?}", std::file!(), std::line!(), std::stringify!($value), &$value);
+        }
     }
 }
 
 #[macro_export]
 macro_rules! println_on_debug {
     ($($rest:tt)*) => {
-        #[cfg(debug_assertions)]
-        std::println!($($rest)*)
+        $crate::log_debug!($($rest)*)
     }
 }
 
@@ -192,6 +224,154 @@ fn compare_paths<P: AsRef<Path> + ?Sized>(path_1: &P, path_2: &P) -> bool {
     true
 }
 
+pub fn is_path_pattern(pattern: &str) -> bool {
+    pattern.contains('*') || pattern.contains('?')
+}
+
+#[cfg(target_family = "unix")]
+pub fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    match path.metadata() {
+        Ok(value) => value.is_file() && value.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn is_executable_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+/**
+   Match given path with pattern such as "/home/me/work" or "/home/*/legacy-*/**".
+   The pattern matches the path itself and every descendant of it.
+   "*" and "?" match characters in one component, and "**" matches any number of components.
+
+   Returns specificity of the pattern if matched, so that the most specific one can be preferred.
+   Each literal component counts 2, each component with wildcards counts 1 and "**" counts nothing.
+**/
+pub fn match_path_pattern(pattern: &str, path: &Path) -> Option<usize> {
+    let pattern_components: Vec<String> = Path::new(pattern).iter()
+        .map(| value | value.to_string_lossy().to_string())
+        .collect();
+
+    if pattern_components.is_empty() {
+        return None;
+    }
+
+    let matched = match is_path_pattern(pattern) {
+        false => Path::new(pattern).contains(path),
+        true => {
+            let path_components: Vec<String> = path.iter()
+                .map(| value | value.to_string_lossy().to_string())
+                .collect();
+
+            match_components(&pattern_components, &path_components).contains(&true)
+        }
+    };
+
+    match matched {
+        false => None,
+        true => {
+            let specificity = pattern_components.iter()
+                .map(| value | match value.as_str() {
+                    "**" => 0,
+                    value2 if is_path_pattern(value2) => 1,
+                    _ => 2,
+                })
+                .sum();
+
+            Some(specificity)
+        }
+    }
+}
+
+/**
+   Returns whether pattern matches each leading part of path, indexed by the number of path components,
+   so that a match of any part means the path is matched or is a descendant of matched one.
+   Rows of the table are built one pattern component at a time, which keeps it polynomial for many "**".
+**/
+fn match_components(pattern: &[String], path: &[String]) -> Vec<bool> {
+    let mut matched: Vec<bool> = vec![false; path.len() + 1];
+    matched[0] = true;
+
+    for value in pattern.iter() {
+        let mut next: Vec<bool> = vec![false; path.len() + 1];
+
+        match value.as_str() {
+            "**" => {
+                let mut any_matched = false;
+
+                for (index, value2) in matched.iter().enumerate() {
+                    any_matched = any_matched || *value2;
+                    next[index] = any_matched;
+                }
+            }
+            _ => {
+                let pattern_chars: Vec<char> = value.chars().collect();
+
+                for (index, value2) in path.iter().enumerate() {
+                    if !matched[index] {
+                        continue;
+                    }
+
+                    let name_chars: Vec<char> = value2.chars().collect();
+                    next[index + 1] = match_wildcard(&pattern_chars, &name_chars);
+                }
+            }
+        }
+
+        matched = next;
+    }
+
+    matched
+}
+
+/**
+   Match a file name with pattern such as "java*" or "j?ell", where "*" and "?" work like in path patterns.
+**/
+pub fn match_name_pattern(pattern: &str, name: &str) -> bool {
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    let name_chars: Vec<char> = name.chars().collect();
+
+    match_wildcard(&pattern_chars, &name_chars)
+}
+
+/**
+   Scans name once, going back only to the last "*" on mismatch instead of trying every split.
+**/
+fn match_wildcard(pattern: &[char], name: &[char]) -> bool {
+    let mut pattern_index: usize = 0;
+    let mut name_index: usize = 0;
+    let mut last_star: Option<(usize, usize)> = None;
+
+    while name_index < name.len() {
+        match pattern.get(pattern_index) {
+            Some('*') => {
+                last_star = Some((pattern_index, name_index));
+                pattern_index += 1;
+            }
+            Some(value) if value.eq(&'?') || value.eq(&name[name_index]) => {
+                pattern_index += 1;
+                name_index += 1;
+            }
+            _ => match last_star {
+                None => {
+                    return false;
+                }
+                Some((value, value2)) => {
+                    last_star = Some((value, value2 + 1));
+                    pattern_index = value + 1;
+                    name_index = value2 + 1;
+                }
+            },
+        }
+    }
+
+    pattern[pattern_index..].iter().all(| value | value.eq(&'*'))
+}
+
 #[cfg(target_family = "windows")]
 pub fn fix_color_options_on_windows() {
     colored::control::set_virtual_terminal(true).unwrap();
@@ -225,6 +405,31 @@ pub fn user_dirs() -> Result<UserDirs, DirectoryResolveError> {
     };
 }
 
+/**
+   Home of the user who ran sudo on Linux, since CLI run by sudo manages config and packages of that user rather than of root.
+**/
+pub fn sudo_user_home_dir() -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    env::vars()
+        .find(| (key, _) | key.to_lowercase().eq("sudo_user"))
+        .map(| (_, value) | PathBuf::from(format!("/home/{}", value)))
+}
+
+/**
+   Home of current user, or of the user who ran sudo. See [sudo_user_home_dir].
+**/
+pub fn user_home_dir() -> Result<PathBuf, DirectoryResolveError> {
+    match sudo_user_home_dir() {
+        None => {}
+        Some(value) => return Ok(value),
+    }
+
+    user_dirs().map(| value | value.home_dir().to_path_buf())
+}
+
 pub trait Upsert<T>
 where
     Self: Sized,
@@ -296,6 +501,46 @@ pub fn project_filename(project_type: ProjectType) -> String {
     return filename.to_string();
 }
 
+/**
+   Canonical path of the runner placed next to the current binary, if it exists.
+**/
+pub fn runner_file_path() -> Option<PathBuf> {
+    let exec_path = env::current_exe().ok()?;
+    let canonical_exec_path = safe_canonicalize(&exec_path).ok()?;
+    let runner_path = canonical_exec_path.pop_path().join(project_filename(ProjectType::ProjectSelectRunner));
+
+    safe_canonicalize(&runner_path).ok()
+}
+
+/**
+   Env var counting how many times runners are nested, so that a runner executing itself stops
+   even when its target starts it as a child process. The limit leaves room for nested runs like recursive builds.
+**/
+pub const RUNNER_DEPTH_ENV_KEY: &str = "PKG_SELECT_RUNNER_DEPTH";
+pub const RUNNER_DEPTH_LIMIT: u32 = 32;
+
+/**
+   Env vars holding exec paths which runners replaced by their targets, and the process id they were replaced in.
+   Runner executed again at a visited exec path in the same process is a loop, while nested runs in child processes are not.
+**/
+pub const RUNNER_VISITED_ENV_KEY: &str = "PKG_SELECT_RUNNER_VISITED";
+pub const RUNNER_PROCESS_ENV_KEY: &str = "PKG_SELECT_RUNNER_PROCESS";
+
+/**
+   Prefix of env vars overriding selected package per category, such as "PKG_SELECT_JDK=zulu-11".
+**/
+pub const PACKAGE_OVERRIDE_ENV_PREFIX: &str = "PKG_SELECT_";
+
+/**
+   Env vars of Package Select sharing [PACKAGE_OVERRIDE_ENV_PREFIX], so that no category can be overridden by them.
+**/
+pub const RESERVED_ENV_KEYS: [&str; 4] = [
+    logger::logger::LOG_ENV_KEY,
+    RUNNER_DEPTH_ENV_KEY,
+    RUNNER_VISITED_ENV_KEY,
+    RUNNER_PROCESS_ENV_KEY,
+];
+
 pub trait PathPop {
     fn pop_path(&self) -> PathBuf;
 }
@@ -376,3 +621,75 @@ impl<T, S> InsertTo<T> for IndexSet<T, S>
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn literal_pattern_matches_itself_and_descendants() {
+        assert_eq!(match_path_pattern("/home/me/work", Path::new("/home/me/work")), Some(8));
+        assert_eq!(match_path_pattern("/home/me/work", Path::new("/home/me/work/src/main")), Some(8));
+        assert_eq!(match_path_pattern("/home/me/work/", Path::new("/home/me/work/src")), Some(8));
+    }
+
+    #[test]
+    fn literal_pattern_does_not_match_siblings_or_parents() {
+        assert_eq!(match_path_pattern("/home/me/work", Path::new("/home/me/workspace")), None);
+        assert_eq!(match_path_pattern("/home/me/work", Path::new("/home/me")), None);
+        assert_eq!(match_path_pattern("", Path::new("/home/me")), None);
+    }
+
+    #[test]
+    fn wildcards_match_in_one_component() {
+        assert!(match_path_pattern("/home/*/legacy-*", Path::new("/home/me/legacy-app/src")).is_some());
+        assert!(match_path_pattern("/home/*/legacy-*", Path::new("/home/me/nested/legacy-app")).is_none());
+        assert!(match_path_pattern("/home/m?/work", Path::new("/home/me/work")).is_some());
+        assert!(match_path_pattern("/home/m?/work", Path::new("/home/mee/work")).is_none());
+    }
+
+    #[test]
+    fn double_star_matches_any_number_of_components() {
+        assert!(match_path_pattern("/home/**/legacy-*", Path::new("/home/legacy-app")).is_some());
+        assert!(match_path_pattern("/home/**/legacy-*", Path::new("/home/me/a/b/legacy-app/src")).is_some());
+        assert!(match_path_pattern("/home/*/legacy-*/**", Path::new("/home/me/legacy-app")).is_some());
+        assert!(match_path_pattern("/home/**/legacy-*", Path::new("/opt/legacy-app")).is_none());
+    }
+
+    #[test]
+    fn specificity_prefers_literal_and_longer_patterns() {
+        let path = Path::new("/home/me/work/service/src");
+
+        let literal = match_path_pattern("/home/me/work/service", path).unwrap();
+        let parent = match_path_pattern("/home/me/work", path).unwrap();
+        let wildcard = match_path_pattern("/home/*/work/service", path).unwrap();
+        let double_star = match_path_pattern("/home/**/service", path).unwrap();
+
+        assert!(literal > parent);
+        assert!(literal > wildcard);
+        assert!(wildcard > double_star);
+    }
+
+    #[test]
+    fn many_double_stars_finish_quickly() {
+        let pattern = format!("/{}/never", vec!["**"; 16].join("/"));
+        let path = PathBuf::from(format!("/{}", vec!["a"; 64].join("/")));
+        let started = Instant::now();
+
+        assert_eq!(match_path_pattern(&pattern, &path), None);
+        assert!(!match_name_pattern(&"*a".repeat(16), &format!("{}b", "a".repeat(64))));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn name_pattern_matches_whole_name() {
+        assert!(match_name_pattern("java*", "javac"));
+        assert!(!match_name_pattern("j?ell", "jshell"));
+        assert!(match_name_pattern("j??ell", "jshell"));
+        assert!(match_name_pattern("*", ""));
+        assert!(match_name_pattern("*c*", "javac"));
+        assert!(!match_name_pattern("java", "javac"));
+        assert!(!match_name_pattern("*.jar", "java"));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::logger::models::log_level::LogLevel;
+use colored::Colorize;
+use std::env;
+use std::fmt::Arguments;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub const LOG_ENV_KEY: &str = "PKG_SELECT_LOG";
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+/**
+   Set log level by [LOG_ENV_KEY] env var such as "PKG_SELECT_LOG=debug", or by given default level if it is not set.
+   Debug builds log everything by default.
+**/
+pub fn init_logger(default_level: LogLevel) {
+    let default_level = match cfg!(debug_assertions) {
+        true => LogLevel::Debug,
+        false => default_level,
+    };
+    let level = env::var(LOG_ENV_KEY).ok()
+        .and_then(| value | value.parse::<LogLevel>().ok())
+        .unwrap_or(default_level);
+
+    set_log_level(level);
+}
+
+pub fn set_log_level(level: LogLevel) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn log_level() -> LogLevel {
+    LogLevel::from_u8(LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+pub fn log_enabled(level: LogLevel) -> bool {
+    level != LogLevel::Off && level <= log_level()
+}
+
+/**
+   Every log is written into stderr, so that stdout of commands and bound executables is kept clean.
+**/
+pub fn log(level: LogLevel, args: Arguments) {
+    if !log_enabled(level) {
+        return;
+    }
+
+    match level {
+        LogLevel::Off => {}
+        LogLevel::Error => eprintln!("{} {}", "error:".bright_red().bold(), args),
+        LogLevel::Warn => eprintln!("{} {}", "warning:".yellow().bold(), args),
+        LogLevel::Info => eprintln!("{}", args),
+        LogLevel::Debug => eprintln!("{} {}", "debug:".dimmed(), args),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod impls;
+// Named like config_resolver::config_resolver, so that logger is imported the same way.
+#[allow(clippy::module_inception)]
+pub mod logger;
+pub mod models;
@@ -0,0 +1,11 @@
+/**
+   Levels are ordered by verbosity, so that a level enables every level before it.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off = 0,
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+}